@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use clap::{Args, ValueEnum};
+use torture_parser::diff::AssetDiff;
+use torture_parser::loader::{Loader, Origin};
+
+use crate::parse_loaded;
+
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Bundles directory of the old version
+    #[arg(value_name = "OLD")]
+    pub old: PathBuf,
+
+    /// Bundles directory of the new version
+    #[arg(value_name = "NEW")]
+    pub new: PathBuf,
+
+    /// Format to print the changes in
+    #[arg(short, long, value_enum, default_value_t = DiffFormat::Text)]
+    pub format: DiffFormat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum DiffFormat {
+    /// One line per change
+    Text,
+    /// Added, removed and changed assets, with the old and new value of every field
+    Json,
+    /// A patch notes section
+    Markdown,
+}
+
+pub fn diff(args: &DiffArgs) -> anyhow::Result<()> {
+    let read = |name: &str, path: &PathBuf| -> anyhow::Result<Vec<_>> {
+        let loader = Loader::new().root(Origin::Custom(name.to_string()), path);
+        #[cfg(feature = "masterbundle")]
+        let loader = crate::list_masterbundles(loader);
+
+        Ok(parse_loaded(&loader)?
+            .into_iter()
+            .map(|parsed| parsed.asset)
+            .collect())
+    };
+    let diff = AssetDiff::new(&read("old", &args.old)?, &read("new", &args.new)?);
+
+    match args.format {
+        DiffFormat::Text => print!("{}", diff),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+        DiffFormat::Markdown => print!("{}", diff.markdown()),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use clap::{Args, ValueEnum};
+use torture_parser::parser::assets::Asset;
+
+use crate::table::{self, Table};
+use crate::ParsedAsset;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Format to write the assets in
+    #[arg(short, long, value_enum, default_value_t = Format::Json)]
+    pub format: Format,
+
+    /// File to write to, instead of stdout. For CSV, the directory to write a file per kind to
+    #[arg(short, long, value_name = "PATH")]
+    pub out: Option<PathBuf>,
+
+    /// Write an SQLite database with a table per kind of asset, instead of using --format
+    #[cfg(feature = "sqlite")]
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["format", "out"])]
+    pub sqlite: Option<PathBuf>,
+
+    /// Maps folder whose spawn tables are added to the SQLite database
+    #[cfg(feature = "sqlite")]
+    #[arg(long, value_name = "PATH", requires = "sqlite")]
+    pub maps: Option<PathBuf>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// An array of every asset
+    Json,
+    /// One asset per line
+    Ndjson,
+    /// A sequence of every asset
+    Yaml,
+    /// An `assets` array of tables, since TOML documents must be tables
+    Toml,
+    /// One file per kind of asset, such as `guns.csv`, with nested fields as dotted columns
+    Csv,
+    /// A workbook with one sheet per kind of asset, laid out like the CSV files
+    #[cfg(feature = "xlsx")]
+    Xlsx,
+}
+
+/// TOML has no top-level arrays.
+#[derive(serde::Serialize)]
+struct TomlDocument<'a> {
+    assets: &'a [&'a Asset],
+}
+
+pub fn export(parsed: &[ParsedAsset], args: &ExportArgs) -> anyhow::Result<()> {
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = &args.sqlite {
+        return crate::sqlite::write(path, parsed, args.maps.as_deref());
+    }
+
+    let assets: Vec<&Asset> = parsed.iter().map(|parsed| &parsed.asset).collect();
+    let assets = assets.as_slice();
+
+    match args.format {
+        Format::Csv => return write_csv(&required_out(args)?, &table::tables(assets)),
+        #[cfg(feature = "xlsx")]
+        Format::Xlsx => return write_xlsx(&required_out(args)?, &table::tables(assets)),
+        _ => {}
+    }
+
+    let mut out: Box<dyn Write> = match &args.out {
+        Some(path) => {
+            Box::new(BufWriter::new(File::create(path).with_context(|| {
+                format!("Failed to create {}", path.display())
+            })?))
+        }
+        None => Box::new(std::io::stdout().lock()),
+    };
+
+    write(&mut out, assets, args.format)?;
+    out.flush().context("Failed to write the export")
+}
+
+pub fn write(out: &mut dyn Write, assets: &[&Asset], format: Format) -> anyhow::Result<()> {
+    match format {
+        Format::Json => {
+            serde_json::to_writer_pretty(&mut *out, assets)?;
+            writeln!(out)?;
+        }
+        Format::Ndjson => {
+            for asset in assets {
+                serde_json::to_writer(&mut *out, asset)?;
+                writeln!(out)?;
+            }
+        }
+        Format::Yaml => serde_yaml::to_writer(out, assets)?,
+        Format::Toml => {
+            let document = toml::to_string(&TomlDocument { assets })
+                .context("Failed to serialize the assets as TOML")?;
+            out.write_all(document.as_bytes())?;
+        }
+        _ => {
+            return Err(anyhow!(
+                "The {:?} format writes files, not a stream",
+                format
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+fn required_out(args: &ExportArgs) -> anyhow::Result<PathBuf> {
+    args.out
+        .clone()
+        .ok_or_else(|| anyhow!("The {:?} format writes files, pass --out", args.format))
+}
+
+/// Writes `<table>.csv` for every table into `directory`.
+pub fn write_csv(directory: &Path, tables: &[Table]) -> anyhow::Result<()> {
+    std::fs::create_dir_all(directory)
+        .with_context(|| format!("Failed to create {}", directory.display()))?;
+
+    for table in tables {
+        let path = directory.join(format!("{}.csv", table.name));
+        let mut writer = csv::Writer::from_path(&path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+
+        writer.write_record(&table.columns)?;
+        for row in &table.rows {
+            writer.write_record(row.iter().map(table::cell_text))?;
+        }
+        writer.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Writes a workbook to `path` with a sheet for every table.
+#[cfg(feature = "xlsx")]
+pub fn write_xlsx(path: &Path, tables: &[Table]) -> anyhow::Result<()> {
+    use rust_xlsxwriter::{Format, Workbook};
+    use serde_json::Value;
+
+    let mut workbook = Workbook::new();
+    let header = Format::new().set_bold();
+
+    for table in tables {
+        let sheet = workbook.add_worksheet();
+        sheet.set_name(&table.name)?;
+        sheet.set_freeze_panes(1, 0)?;
+
+        for (column, name) in table.columns.iter().enumerate() {
+            sheet.write_string_with_format(0, column as u16, name, &header)?;
+        }
+
+        for (row, values) in table.rows.iter().enumerate() {
+            let row = row as u32 + 1;
+            for (column, value) in values.iter().enumerate() {
+                let column = column as u16;
+                match value {
+                    Value::Null => {}
+                    Value::Bool(value) => {
+                        sheet.write_boolean(row, column, *value)?;
+                    }
+                    Value::Number(number) => {
+                        sheet.write_number(row, column, number.as_f64().unwrap_or_default())?;
+                    }
+                    value => {
+                        sheet.write_string(row, column, table::cell_text(value))?;
+                    }
+                }
+            }
+        }
+    }
+
+    workbook
+        .save(path)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
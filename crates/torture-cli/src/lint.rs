@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use torture_parser::lint::Severity;
+use torture_parser::loader::Loader;
+use torture_parser::source::FileSystem;
+
+use crate::parse_loaded;
+
+#[derive(Args)]
+pub struct LintArgs {
+    /// Folder of the content to lint, such as a mod
+    #[arg(value_name = "MOD")]
+    pub path: PathBuf,
+
+    /// Bundles directory of the game, to check overrides, references and calibers against
+    #[arg(long, value_name = "PATH")]
+    pub vanilla: Option<PathBuf>,
+
+    /// Fail on warnings as well as errors
+    #[arg(long)]
+    pub deny_warnings: bool,
+}
+
+/// Prints every lint, and exits with status 1 when any of them fail the run, for CI.
+pub fn lint(args: &LintArgs) -> anyhow::Result<()> {
+    let vanilla = match &args.vanilla {
+        Some(path) => {
+            let loader = Loader::new().vanilla(path);
+            #[cfg(feature = "masterbundle")]
+            let loader = crate::list_masterbundles(loader);
+
+            parse_loaded(&loader)?
+                .into_iter()
+                .map(|parsed| parsed.asset)
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    let lints = torture_parser::lint::lint(&FileSystem, &args.path, &vanilla)?;
+    for lint in &lints {
+        println!("{}\n", lint);
+    }
+
+    let errors = lints
+        .iter()
+        .filter(|lint| lint.severity() == Severity::Error)
+        .count();
+    let warnings = lints.len() - errors;
+    println!("{} errors, {} warnings", errors, warnings);
+
+    if errors > 0 || (args.deny_warnings && warnings > 0) {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
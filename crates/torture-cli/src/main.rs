@@ -1,150 +1,207 @@
+mod diff;
+mod export;
+mod lint;
+mod query;
+mod show;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod table;
+
+use std::path::PathBuf;
+
 use anyhow::{anyhow, Context};
-use clap::Parser;
-use masterbundle_collector::MasterBundle;
-use std::path::{Path, PathBuf};
-use torture_parser::parser::assets::gear::ItemGearAsset;
-use torture_parser::parser::assets::glasses::ItemGlassesAsset;
-use torture_parser::parser::assets::mask::ItemMaskAsset;
-use torture_parser::parser::assets::parachute::ItemParachuteAsset;
-
-use torture_parser::parser::assets::bag::ItemBagAsset;
-use torture_parser::parser::assets::barrel::ItemBarrelAsset;
-use torture_parser::parser::assets::barricade::ItemBarricadeAsset;
-use torture_parser::parser::assets::consumable::ItemConsumableAsset;
-use torture_parser::parser::assets::grip::ItemGripAsset;
-use torture_parser::parser::assets::magazine::ItemMagazineAsset;
-use torture_parser::parser::assets::optic::ItemOpticAsset;
-use torture_parser::parser::assets::sentry::ItemSentryAsset;
-use torture_parser::parser::assets::shirt::ItemShirtAsset;
-use torture_parser::parser::assets::sight::ItemSightAsset;
-use torture_parser::parser::assets::structure::ItemStructureAsset;
-use torture_parser::parser::assets::tactical::ItemTacticalAsset;
-use torture_parser::parser::assets::Type;
-
-use torture_parser::get_file_stem;
-use torture_parser::parser::{
-    assets::{gun::ItemGunAsset, BaseAsset},
-    Parser as TortureParser,
-};
+use clap::{Parser, Subcommand};
+use torture_parser::loader::{LoadFailure, Loader, Origin};
+use torture_parser::parser::assets::{AsBaseAsset, Asset, AssetResult, Type};
+use torture_parser::source::FileSystem;
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, subcommand_negates_reqs = true)]
 pub struct Cli {
-    /// Path to the bundles directory
+    /// Path to the bundles directory, which every command but `diff` and `lint` needs
+    #[arg(short, long, value_name = "PATH", required = true)]
+    pub path: Option<PathBuf>,
+
+    /// Steam workshop folder, whose mods load after the bundles directory
     #[arg(short, long, value_name = "PATH")]
-    pub path: PathBuf,
+    pub workshop: Option<PathBuf>,
+
+    /// Server bundles directory, which loads after every mod
+    #[arg(short, long, value_name = "PATH")]
+    pub server: Option<PathBuf>,
+
+    /// Print which assets shadow others in load order, instead of every asset
+    #[arg(long)]
+    pub conflicts: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Serialize every asset for other programs to read
+    Export(export::ExportArgs),
+
+    /// Compare the assets of two bundles directories, such as two game or mod versions
+    Diff(diff::DiffArgs),
+
+    /// Check content for mistakes, such as typos in keys and references to missing assets
+    Lint(lint::LintArgs),
+
+    /// Print the assets matching a filter, such as `type = Gun and firemodes contains Auto`
+    Query(query::QueryArgs),
+
+    /// Print one asset's fields, with the names of the assets it refers to and what spawns it
+    Show(show::ShowArgs),
 }
 
 fn main() -> anyhow::Result<()> {
     let args = Cli::parse();
 
-    let bundle = MasterBundle::new(args.path.clone())?;
-    let mut paths: Vec<&Path> = bundle
-        .assets
-        .iter()
-        .map(|x| x.parent().unwrap())
-        .filter(|p| !p.as_os_str().is_empty() && *p != Path::new(".") && *p != Path::new(".."))
-        .collect();
-
-    paths.sort_by_key(|path| get_file_stem(path).unwrap_or(path.to_str().unwrap().to_string()));
-    paths.dedup_by(|a, b| a == b);
-
-    for path in paths {
-        let mut data_path: PathBuf = args.path.join(path);
-
-        let stem = path
-            .file_stem()
-            .ok_or_else(|| anyhow!("Failed to get file stem for: {:#?}", path))?;
-        let stem = stem
-            .to_str()
-            .ok_or_else(|| anyhow!("Failed to convert into a &str"))?;
-        data_path.push(format!("{}.dat", stem));
-
-        if let Ok(content) = std::fs::read_to_string(&data_path) {
-            let directory = data_path
-                .parent()
-                .context("Failed to get the parent of data file")?;
-
-            if let Ok(asset) = BaseAsset::parse(directory, &content) {
-                match asset.r#type {
-                    Type::Backpack | Type::Pants | Type::Vest => {
-                        let bwa = ItemBagAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Gun => {
-                        let bwa = ItemGunAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Sight => {
-                        let bwa = ItemSightAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Tactical => {
-                        let bwa = ItemTacticalAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Grip => {
-                        let bwa = ItemGripAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Barrel => {
-                        let bwa = ItemBarrelAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Magazine => {
-                        let bwa = ItemMagazineAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Optic => {
-                        let bwa = ItemOpticAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Medical => {
-                        let bwa = ItemConsumableAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Barricade => {
-                        let bwa = ItemBarricadeAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Structure => {
-                        let bwa = ItemStructureAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Storage => {
-                        let bwa = ItemStructureAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Sentry => {
-                        let bwa = ItemSentryAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Shirt => {
-                        let bwa = ItemShirtAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Cloud => {
-                        let bwa = ItemParachuteAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Glasses => {
-                        let bwa = ItemGlassesAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Mask => {
-                        let bwa = ItemMaskAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Hat => {
-                        let bwa = ItemGearAsset::parse(directory, &content)?;
-                        println!("{:#?}", bwa);
-                    }
-                    Type::Unknown => {}
-                    _ => println!("{:#?}", asset),
-                }
+    if args.conflicts {
+        return print_conflicts(&args);
+    }
+
+    match &args.command {
+        Some(Command::Export(export)) => export::export(&parse_assets(&args)?, export),
+        Some(Command::Diff(diff)) => diff::diff(diff),
+        Some(Command::Lint(lint)) => lint::lint(lint),
+        Some(Command::Query(query)) => query::query(&parse_assets(&args)?, query),
+        Some(Command::Show(show)) => show::show(&parse_assets(&args)?, show),
+        None => {
+            for parsed in parse_assets(&args)? {
+                println!("{:#?}", parsed.asset);
             }
+
+            Ok(())
+        }
+    }
+}
+
+/// An asset along with where it was read from.
+pub struct ParsedAsset {
+    pub origin: Origin,
+    pub data_file: PathBuf,
+    pub asset: Asset,
+}
+
+/// Roots to read, in load order: the bundles directory, then workshop mods, then the server.
+fn loader(args: &Cli) -> anyhow::Result<Loader> {
+    let path = args
+        .path
+        .as_deref()
+        .ok_or_else(|| anyhow!("--path is required"))?;
+    let mut loader = Loader::new().vanilla(path);
+    if let Some(workshop) = &args.workshop {
+        loader = loader.workshop(workshop)?;
+    }
+    if let Some(server) = &args.server {
+        loader = loader.server(server);
+    }
+    #[cfg(feature = "masterbundle")]
+    let loader = list_masterbundles(loader);
+
+    Ok(loader)
+}
+
+/// Also loads the assets listed by the masterbundle of each root, if it has one.
+#[cfg(feature = "masterbundle")]
+fn list_masterbundles(mut loader: Loader) -> Loader {
+    use masterbundle_collector::MasterBundle;
+
+    for root in loader.roots().to_vec() {
+        if let Ok(bundle) = MasterBundle::new(root.path.clone()) {
+            let listed: Vec<PathBuf> = bundle
+                .assets
+                .iter()
+                .filter_map(|asset| asset.parent())
+                .map(PathBuf::from)
+                .collect();
+            loader = loader.listed(&root.path, listed);
+        }
+    }
+
+    loader
+}
+
+/// Parses every asset left after overrides, see [`parse_loaded`].
+fn parse_assets(args: &Cli) -> anyhow::Result<Vec<ParsedAsset>> {
+    parse_loaded(&loader(args)?)
+}
+
+/// Parses every asset of the [`Catalog`](torture_parser::loader::Catalog) of `loader`, so
+/// assets shadowed by a later root are left out. Assets that fail to load or parse are
+/// reported to stderr.
+///
+/// Assets of an unknown type are left out.
+fn parse_loaded(loader: &Loader) -> anyhow::Result<Vec<ParsedAsset>> {
+    let catalog = loader.load()?;
+    catalog.failures.iter().for_each(report);
+
+    let mut assets = Vec::new();
+    // Assets of the same data file are next to each other, so it is only parsed once.
+    let mut file: Option<(PathBuf, Vec<Option<AssetResult>>)> = None;
+
+    for loaded in catalog.assets {
+        if file.as_ref().map(|(path, _)| path) != Some(&loaded.data_file) {
+            let parsed = std::fs::read_to_string(&loaded.data_file)
+                .context("Failed to read data file")
+                .and_then(|content| Asset::parse_all(&FileSystem, loaded.directory(), &content))
+                .unwrap_or_else(|error| {
+                    report(&failure(&loaded.origin, &loaded.data_file, error));
+                    Vec::new()
+                });
+            file = Some((
+                loaded.data_file.clone(),
+                parsed.into_iter().map(Some).collect(),
+            ));
+        }
+        let Some(parsed) = file
+            .as_mut()
+            .and_then(|(_, parsed)| parsed.get_mut(loaded.block))
+            .and_then(Option::take)
+        else {
+            continue;
+        };
+
+        match parsed {
+            Ok((asset, _)) if matches!(asset.base_asset().r#type, Type::Unknown) => {}
+            Ok((asset, _)) => assets.push(ParsedAsset {
+                origin: loaded.origin,
+                data_file: loaded.data_file,
+                asset,
+            }),
+            Err(error) => report(&failure(&loaded.origin, &loaded.data_file, error)),
         }
     }
 
+    Ok(assets)
+}
+
+fn failure(origin: &Origin, data_file: &std::path::Path, error: anyhow::Error) -> LoadFailure {
+    LoadFailure {
+        origin: origin.clone(),
+        data_file: data_file.to_path_buf(),
+        error: format!("{:#}", error),
+    }
+}
+
+fn report(failure: &LoadFailure) {
+    eprintln!(
+        "{} {}: {}",
+        failure.origin,
+        failure.data_file.display(),
+        failure.error
+    );
+}
+
+fn print_conflicts(args: &Cli) -> anyhow::Result<()> {
+    let catalog = loader(args)?.load()?;
+    for conflict in &catalog.conflicts {
+        println!("{}", conflict);
+    }
+    catalog.failures.iter().for_each(report);
+
     Ok(())
 }
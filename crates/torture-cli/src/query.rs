@@ -0,0 +1,127 @@
+use clap::{Args, ValueEnum};
+use serde_json::{Map, Value};
+use torture_parser::query::{self, Fields, Filter};
+
+use crate::ParsedAsset;
+
+#[derive(Args)]
+pub struct QueryArgs {
+    /// Filter such as `type = Gun and player_damage.amount > 40`, every asset matches if left out
+    #[arg(value_name = "FILTER")]
+    pub filter: Option<Filter>,
+
+    /// Field to sort the matches by, assets without it come last
+    #[arg(long, value_name = "FIELD")]
+    pub sort: Option<String>,
+
+    /// Sort in descending order
+    #[arg(long, requires = "sort")]
+    pub desc: bool,
+
+    /// Print at most this many matches
+    #[arg(long, value_name = "N")]
+    pub limit: Option<usize>,
+
+    /// Fields to print, separated by commas
+    #[arg(
+        long,
+        value_name = "FIELDS",
+        value_delimiter = ',',
+        default_value = "kind,id,name"
+    )]
+    pub fields: Vec<String>,
+
+    /// Format to print the matches in
+    #[arg(short, long, value_enum, default_value_t = QueryFormat::Text)]
+    pub format: QueryFormat,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, ValueEnum)]
+pub enum QueryFormat {
+    /// Aligned columns with a header
+    Text,
+    /// An array of objects with the chosen fields
+    Json,
+}
+
+pub fn query(parsed: &[ParsedAsset], args: &QueryArgs) -> anyhow::Result<()> {
+    let mut matches: Vec<Fields> = parsed
+        .iter()
+        .map(|parsed| query::fields(&parsed.asset))
+        .filter(|fields| {
+            args.filter
+                .as_ref()
+                .is_none_or(|filter| filter.matches(fields))
+        })
+        .collect();
+
+    if let Some(sort) = &args.sort {
+        // Missing values stay last either way.
+        matches.sort_by(|a, b| {
+            let (a, b) = (field(a, sort), field(b, sort));
+            match (a.is_null(), b.is_null(), args.desc) {
+                (false, false, true) => query::compare_values(b, a),
+                _ => query::compare_values(a, b),
+            }
+        });
+    }
+    if let Some(limit) = args.limit {
+        matches.truncate(limit);
+    }
+
+    let rows: Vec<Vec<&Value>> = matches
+        .iter()
+        .map(|fields| args.fields.iter().map(|name| field(fields, name)).collect())
+        .collect();
+
+    match args.format {
+        QueryFormat::Text => print_columns(&args.fields, &rows),
+        QueryFormat::Json => {
+            let objects: Vec<Map<String, Value>> = rows
+                .into_iter()
+                .map(|row| {
+                    args.fields
+                        .iter()
+                        .cloned()
+                        .zip(row.into_iter().cloned())
+                        .collect()
+                })
+                .collect();
+            println!("{}", serde_json::to_string_pretty(&objects)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn field<'a>(fields: &'a Fields, name: &str) -> &'a Value {
+    fields.get(name).unwrap_or(&Value::Null)
+}
+
+fn print_columns(header: &[String], rows: &[Vec<&Value>]) {
+    let rows: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.iter().map(|value| query::value_text(value)).collect())
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|name| name.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let print_row = |cells: &[String]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    };
+
+    print_row(header);
+    for row in &rows {
+        print_row(row);
+    }
+}
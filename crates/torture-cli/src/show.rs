@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+
+use anyhow::bail;
+use clap::Args;
+use torture_parser::diff::AssetSummary;
+use torture_parser::loader::AssetCategory;
+use torture_parser::lookup::Lookup;
+use torture_parser::parser::assets::{AsBaseAsset, Asset};
+use torture_parser::parser::blueprint::{Blueprint, BlueprintItem};
+use torture_parser::parser::reference::AssetReference;
+use torture_parser::query;
+
+use crate::ParsedAsset;
+
+#[derive(Args)]
+pub struct ShowArgs {
+    /// Legacy ID, GUID or name of the asset
+    #[arg(value_name = "ASSET")]
+    pub asset: String,
+
+    /// Data file of the asset, or the end of its path, to pick one of several matches
+    #[arg(long, value_name = "PATH")]
+    pub file: Option<PathBuf>,
+}
+
+/// Prints every field of one asset, with the names of the assets it refers to, followed by the
+/// magazine replacements and attachments of guns, its blueprints, and the spawn assets that can
+/// roll it.
+pub fn show(parsed: &[ParsedAsset], args: &ShowArgs) -> anyhow::Result<()> {
+    let assets: Vec<&Asset> = parsed.iter().map(|parsed| &parsed.asset).collect();
+    let lookup = Lookup::new(&assets);
+
+    let mut found = lookup.find(&args.asset);
+    if let Some(file) = &args.file {
+        found.retain(|&index| parsed[index].data_file.ends_with(file));
+    }
+    // IDs are only unique within a category, and a bare ID most likely means an item.
+    let items: Vec<usize> = found
+        .iter()
+        .copied()
+        .filter(|&index| {
+            AssetCategory::of(parsed[index].asset.base_asset().r#type) == AssetCategory::Item
+        })
+        .collect();
+    if items.len() == 1 {
+        found = items;
+    }
+    let shown = match found.as_slice() {
+        [] => bail!("No asset matches `{}`", args.asset),
+        [index] => &parsed[*index],
+        _ => {
+            eprintln!("`{}` matches {} assets:", args.asset, found.len());
+            for index in found {
+                let shown = &parsed[index];
+                eprintln!(
+                    "  {} {} {}",
+                    AssetSummary::of(&shown.asset),
+                    shown.origin,
+                    shown.data_file.display()
+                );
+            }
+
+            bail!("Pick one of them with --file");
+        }
+    };
+    let asset = &shown.asset;
+
+    println!("{}", AssetSummary::of(asset));
+    println!("{} {}", shown.origin, shown.data_file.display());
+
+    println!();
+    let fields = query::fields(asset);
+    let width = fields.keys().map(String::len).max().unwrap_or_default();
+    // The kind is part of the summary, and blueprints and magazine replacements are listed with
+    // their item names below.
+    let skipped = |field: &str| matches!(field, "kind" | "blueprints" | "magazine_replacements");
+    for (field, value) in fields.iter().filter(|(field, _)| !skipped(field)) {
+        let text = query::value_text(value);
+        let reference = text.parse::<AssetReference<Asset>>();
+        let text = match reference {
+            Ok(reference) if field.ends_with("_id") && !reference.is_none() => {
+                item_text(&lookup, reference)
+            }
+            _ => text,
+        };
+
+        println!("{:width$}  {}", field, text, width = width);
+    }
+
+    if let Asset::Gun(gun) = asset {
+        if !gun.magazine_replacements.is_empty() {
+            println!("\nMagazine replacements");
+            for replacement in &gun.magazine_replacements {
+                println!(
+                    "  {}: {}",
+                    replacement.map,
+                    item_text(&lookup, replacement.id)
+                );
+            }
+        }
+
+        let attachments = lookup.attachments(gun);
+        if !attachments.is_empty() {
+            println!("\nAttachments");
+            for attachment in attachments {
+                println!("  {}", AssetSummary::of(attachment));
+            }
+        }
+    }
+
+    let blueprints = &asset.base_asset().blueprints;
+    if !blueprints.is_empty() {
+        println!("\nBlueprints");
+        for blueprint in blueprints {
+            println!("  {}", blueprint_text(&lookup, blueprint));
+        }
+    }
+
+    let rolled_by = lookup.spawns_of(asset);
+    if !rolled_by.is_empty() {
+        println!("\nSpawn tables");
+        for spawn in rolled_by {
+            println!("  spawn {} ({})", spawn.id(), spawn.name());
+        }
+    }
+
+    Ok(())
+}
+
+/// Formats an item reference as e.g. `1165 (Military Magazine)`, or only the ID or GUID when
+/// no parsed asset has it.
+fn item_text<T>(lookup: &Lookup, reference: AssetReference<T>) -> String {
+    match lookup.resolve(reference, AssetCategory::Item) {
+        Some(asset) => format!("{} ({})", reference, asset.name()),
+        None => reference.to_string(),
+    }
+}
+
+/// Formats a blueprint as e.g. `Supply: 2 × 66 (Cloth) → 2 × 95 (Bandage)`.
+fn blueprint_text(lookup: &Lookup, blueprint: &Blueprint) -> String {
+    let items = |items: &[BlueprintItem]| {
+        let items: Vec<String> = items
+            .iter()
+            .map(|item| format!("{} × {}", item.amount, item_text(lookup, item.item)))
+            .collect();
+        items.join(", ")
+    };
+
+    let mut text = format!(
+        "{}: {} → {}",
+        blueprint.r#type,
+        items(&blueprint.supplies),
+        items(&blueprint.outputs)
+    );
+    if !blueprint.tool.is_none() {
+        text.push_str(&format!(", using {}", item_text(lookup, blueprint.tool)));
+    }
+    if blueprint.level > 0 {
+        text.push_str(&format!(", needs {} {}", blueprint.skill, blueprint.level));
+    }
+
+    text
+}
@@ -0,0 +1,370 @@
+//! Writes assets into an SQLite database, for programs that query assets instead of parsing
+//! the bundles on every start.
+//!
+//! - `assets` holds what every asset has, keyed by `asset_id`.
+//! - A stats table per kind, such as `guns`, holds the columns of the CSV export.
+//! - `localization`, `calibers` and `hooks` link rows to `assets`.
+//! - `blueprints` lists the crafting recipes of each item, and `blueprint_items` what each
+//!   one uses up, needs and makes.
+//! - `spawn_table_entries` lists what each spawn table of each map can spawn, by legacy ID.
+
+use std::path::Path;
+
+use anyhow::Context;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter, Connection};
+use serde_json::Value;
+use torture_parser::parser::assets::{AsBaseAsset, AsCaliber, Asset};
+use torture_parser::parser::blueprint::{Blueprint, BlueprintItem};
+use torture_parser::parser::level::spawns::SpawnTable;
+use torture_parser::parser::level::Level;
+
+use crate::table::{self, Table};
+use crate::ParsedAsset;
+
+const SCHEMA: &str = "
+CREATE TABLE assets (
+    asset_id INTEGER PRIMARY KEY,
+    guid TEXT,
+    id INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    type TEXT NOT NULL,
+    rarity TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    source TEXT NOT NULL,
+    data_file TEXT NOT NULL
+);
+CREATE INDEX assets_guid ON assets (guid);
+CREATE INDEX assets_id ON assets (id);
+
+CREATE TABLE localization (
+    asset_id INTEGER NOT NULL REFERENCES assets (asset_id),
+    language TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL
+);
+
+-- `role` is `caliber` for attachments and magazines, or `magazine` and `attachment` for the
+-- calibers a gun accepts.
+CREATE TABLE calibers (
+    asset_id INTEGER NOT NULL REFERENCES assets (asset_id),
+    role TEXT NOT NULL,
+    caliber INTEGER NOT NULL
+);
+
+CREATE TABLE hooks (
+    asset_id INTEGER NOT NULL REFERENCES assets (asset_id),
+    hook TEXT NOT NULL
+);
+
+CREATE TABLE blueprints (
+    blueprint_id INTEGER PRIMARY KEY,
+    asset_id INTEGER NOT NULL REFERENCES assets (asset_id),
+    type TEXT NOT NULL,
+    skill TEXT NOT NULL,
+    level INTEGER NOT NULL
+);
+
+-- `role` is `supply` for items used up, `tool` for the item needed but kept, or `output` for
+-- items made. Items are referred to by either their legacy `item_id` or their `item_guid`.
+CREATE TABLE blueprint_items (
+    blueprint_id INTEGER NOT NULL REFERENCES blueprints (blueprint_id),
+    role TEXT NOT NULL,
+    item_id INTEGER,
+    item_guid TEXT,
+    amount INTEGER NOT NULL
+);
+
+-- `category` is `item`, `vehicle` or `animal`, which `id` is the legacy ID of.
+CREATE TABLE spawn_table_entries (
+    map TEXT NOT NULL,
+    category TEXT NOT NULL,
+    spawn_table TEXT NOT NULL,
+    table_id INTEGER NOT NULL,
+    tier TEXT NOT NULL,
+    chance REAL NOT NULL,
+    id INTEGER NOT NULL
+);
+";
+
+/// Columns stored in `assets` or in a link table, instead of the stats tables.
+const SHARED_COLUMNS: [&str; 11] = [
+    "id",
+    "guid",
+    "name",
+    "description",
+    "type",
+    "rarity",
+    "calibers",
+    "magazine_calibers",
+    "attachment_calibers",
+    "hooks",
+    "blueprints",
+];
+
+/// Writes a new database to `path`, replacing any file there.
+///
+/// `maps` is a `Maps` folder, whose spawn tables are added when given.
+pub fn write(path: &Path, parsed: &[ParsedAsset], maps: Option<&Path>) -> anyhow::Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("Failed to replace {}", path.display()))?;
+    }
+
+    let mut connection =
+        Connection::open(path).with_context(|| format!("Failed to create {}", path.display()))?;
+    let transaction = connection.transaction()?;
+    transaction.execute_batch(SCHEMA)?;
+
+    let mut asset_ids = Vec::with_capacity(parsed.len());
+    for parsed in parsed {
+        asset_ids.push(insert_asset(&transaction, parsed)?);
+    }
+
+    let assets: Vec<&Asset> = parsed.iter().map(|parsed| &parsed.asset).collect();
+    for table in table::tables(&assets) {
+        insert_stats(&transaction, &table, &asset_ids)?;
+    }
+
+    if let Some(maps) = maps {
+        insert_spawn_tables(&transaction, maps)?;
+    }
+
+    transaction.commit()?;
+    Ok(())
+}
+
+fn insert_asset(connection: &Connection, parsed: &ParsedAsset) -> anyhow::Result<i64> {
+    let asset = &parsed.asset;
+    let base = asset.base_asset();
+    let guid = (!base.guid.is_nil()).then(|| base.guid.to_string());
+
+    connection.execute(
+        "INSERT INTO assets (guid, id, kind, type, rarity, name, description, source, data_file)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            guid,
+            base.id,
+            asset.kind(),
+            format!("{:?}", base.r#type),
+            format!("{:?}", base.rarity),
+            base.name,
+            base.description,
+            parsed.origin.to_string(),
+            parsed.data_file.display().to_string(),
+        ],
+    )?;
+    let asset_id = connection.last_insert_rowid();
+
+    // Only English is read for now.
+    connection.execute(
+        "INSERT INTO localization (asset_id, language, name, description) VALUES (?1, ?2, ?3, ?4)",
+        params![asset_id, "English", base.name, base.description],
+    )?;
+
+    let mut calibers: Vec<(&str, u16)> = Vec::new();
+    let mut hooks = Vec::new();
+    match asset {
+        Asset::Gun(gun) => {
+            calibers.extend(gun.magazine_calibers.iter().map(|id| ("magazine", *id)));
+            calibers.extend(gun.attachment_calibers.iter().map(|id| ("attachment", *id)));
+            hooks.extend(gun.hooks.iter().map(|hook| format!("{:?}", hook)));
+        }
+        Asset::Barrel(asset) => calibers.extend(caliber_rows(asset)),
+        Asset::Grip(asset) => calibers.extend(caliber_rows(asset)),
+        Asset::Magazine(asset) => calibers.extend(caliber_rows(asset)),
+        Asset::Sight(asset) => calibers.extend(caliber_rows(asset)),
+        Asset::Tactical(asset) => calibers.extend(caliber_rows(asset)),
+        _ => {}
+    }
+
+    for (role, caliber) in calibers {
+        connection.execute(
+            "INSERT INTO calibers (asset_id, role, caliber) VALUES (?1, ?2, ?3)",
+            params![asset_id, role, caliber],
+        )?;
+    }
+    for hook in hooks {
+        connection.execute(
+            "INSERT INTO hooks (asset_id, hook) VALUES (?1, ?2)",
+            params![asset_id, hook],
+        )?;
+    }
+
+    for blueprint in &base.blueprints {
+        insert_blueprint(connection, asset_id, blueprint)?;
+    }
+
+    Ok(asset_id)
+}
+
+fn insert_blueprint(
+    connection: &Connection,
+    asset_id: i64,
+    blueprint: &Blueprint,
+) -> anyhow::Result<()> {
+    connection.execute(
+        "INSERT INTO blueprints (asset_id, type, skill, level) VALUES (?1, ?2, ?3, ?4)",
+        params![asset_id, blueprint.r#type, blueprint.skill, blueprint.level],
+    )?;
+    let blueprint_id = connection.last_insert_rowid();
+
+    let tool = (!blueprint.tool.is_none()).then_some(BlueprintItem {
+        item: blueprint.tool,
+        amount: 1,
+    });
+    let items = blueprint
+        .supplies
+        .iter()
+        .map(|item| ("supply", item))
+        .chain(tool.iter().map(|item| ("tool", item)))
+        .chain(blueprint.outputs.iter().map(|item| ("output", item)));
+
+    for (role, item) in items {
+        connection.execute(
+            "INSERT INTO blueprint_items (blueprint_id, role, item_id, item_guid, amount)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                blueprint_id,
+                role,
+                item.item.id(),
+                item.item.guid().map(|guid| guid.to_string()),
+                item.amount
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+fn caliber_rows<T: AsCaliber>(asset: &T) -> impl Iterator<Item = (&'static str, u16)> + '_ {
+    asset.caliber().calibers.iter().map(|id| ("caliber", *id))
+}
+
+/// Creates the stats table of a kind, typing each column by the values in it.
+fn insert_stats(connection: &Connection, table: &Table, asset_ids: &[i64]) -> anyhow::Result<()> {
+    let columns: Vec<usize> = (0..table.columns.len())
+        .filter(|&column| !SHARED_COLUMNS.contains(&table.columns[column].as_str()))
+        .collect();
+
+    let definitions: Vec<String> = columns
+        .iter()
+        .map(|&column| {
+            let values = table.rows.iter().map(|row| &row[column]);
+            format!("{} {}", quote(&table.columns[column]), column_type(values))
+        })
+        .collect();
+    connection.execute_batch(&format!(
+        "CREATE TABLE {} (asset_id INTEGER PRIMARY KEY REFERENCES assets (asset_id){});",
+        quote(&table.name),
+        definitions
+            .iter()
+            .map(|definition| format!(", {}", definition))
+            .collect::<String>()
+    ))?;
+
+    let placeholders: String = columns.iter().map(|_| ", ?").collect();
+    let names: String = columns
+        .iter()
+        .map(|&column| format!(", {}", quote(&table.columns[column])))
+        .collect();
+    let mut insert = connection.prepare(&format!(
+        "INSERT INTO {} (asset_id{}) VALUES (?{})",
+        quote(&table.name),
+        names,
+        placeholders
+    ))?;
+
+    for (row, &asset) in table.rows.iter().zip(&table.assets) {
+        let values = std::iter::once(SqlValue::Integer(asset_ids[asset]))
+            .chain(columns.iter().map(|&column| sql_value(&row[column])));
+        insert.execute(params_from_iter(values))?;
+    }
+
+    Ok(())
+}
+
+fn insert_spawn_tables(connection: &Connection, maps: &Path) -> anyhow::Result<()> {
+    let entries = std::fs::read_dir(maps)
+        .with_context(|| format!("Failed to read directory: {}", maps.display()))?;
+
+    let mut insert = connection.prepare(
+        "INSERT INTO spawn_table_entries (map, category, spawn_table, table_id, tier, chance, id)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+
+    for entry in entries {
+        // Every map has a `Config.json`, other folders are skipped.
+        let path = entry?.path();
+        if !path.join("Config.json").is_file() {
+            continue;
+        }
+
+        let level = match Level::parse_level(&path) {
+            Ok(level) => level,
+            Err(error) => {
+                eprintln!("{}: {:#}", path.display(), error);
+                continue;
+            }
+        };
+
+        let categories: [(&str, &[SpawnTable]); 3] = [
+            ("item", &level.item_tables),
+            ("vehicle", &level.vehicle_tables),
+            ("animal", &level.animal_tables),
+        ];
+        for (category, tables) in categories {
+            for table in tables {
+                for tier in &table.tiers {
+                    for id in &tier.ids {
+                        insert.execute(params![
+                            level.name,
+                            category,
+                            table.name,
+                            table.table_id,
+                            tier.name,
+                            tier.chance,
+                            id
+                        ])?;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `INTEGER` when every value is a whole number or a bool, `REAL` for other numbers, and
+/// `TEXT` otherwise.
+fn column_type<'a>(values: impl Iterator<Item = &'a Value>) -> &'static str {
+    let mut column_type = "INTEGER";
+
+    for value in values {
+        match value {
+            Value::Null | Value::Bool(_) => {}
+            Value::Number(number) if number.is_i64() || number.is_u64() => {}
+            Value::Number(_) => column_type = "REAL",
+            _ => return "TEXT",
+        }
+    }
+
+    column_type
+}
+
+fn sql_value(value: &Value) -> SqlValue {
+    match value {
+        Value::Null => SqlValue::Null,
+        Value::Bool(value) => SqlValue::Integer(*value as i64),
+        Value::Number(number) => match number.as_i64() {
+            Some(number) => SqlValue::Integer(number),
+            None => SqlValue::Real(number.as_f64().unwrap_or_default()),
+        },
+        value => SqlValue::Text(table::cell_text(value)),
+    }
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
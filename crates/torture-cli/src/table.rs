@@ -0,0 +1,92 @@
+//! Flattens assets into one table per kind, for spreadsheets and databases.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+use torture_parser::parser::assets::Asset;
+use torture_parser::query::{self, Fields};
+
+/// Columns shown first, since they identify the asset. Every other column is sorted by name,
+/// so the order does not change between runs.
+const LEADING_COLUMNS: [&str; 3] = ["id", "guid", "name"];
+
+/// Every asset of one kind, such as `guns`.
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<Value>>,
+
+    /// Index of the asset of each row, in the slice given to [`tables`].
+    #[cfg_attr(not(feature = "sqlite"), allow(dead_code))]
+    pub assets: Vec<usize>,
+}
+
+/// Groups assets by kind, in the order of [`Asset::KINDS`]. Kinds without assets are left out.
+///
+/// Nested fields are dotted columns, such as `player_damage.skull_multiplier`, see
+/// [`query::fields`].
+pub fn tables(assets: &[&Asset]) -> Vec<Table> {
+    let mut by_kind: BTreeMap<&str, Vec<(usize, Fields)>> = BTreeMap::new();
+    for (index, asset) in assets.iter().enumerate() {
+        let mut row = query::fields(asset);
+        row.remove("kind");
+
+        by_kind.entry(asset.kind()).or_default().push((index, row));
+    }
+
+    Asset::KINDS
+        .iter()
+        .filter_map(|kind| by_kind.remove(kind).map(|rows| table(kind, rows)))
+        .collect()
+}
+
+fn table(kind: &str, rows: Vec<(usize, Fields)>) -> Table {
+    let (assets, rows): (Vec<usize>, Vec<_>) = rows.into_iter().unzip();
+    let names: BTreeSet<&String> = rows.iter().flat_map(|row| row.keys()).collect();
+    let mut columns: Vec<String> = LEADING_COLUMNS
+        .iter()
+        .filter(|column| names.iter().any(|name| name == *column))
+        .map(|column| column.to_string())
+        .collect();
+    columns.extend(
+        names
+            .into_iter()
+            .filter(|name| !LEADING_COLUMNS.contains(&name.as_str()))
+            .cloned(),
+    );
+
+    let rows = rows
+        .into_iter()
+        .map(|mut row| {
+            columns
+                .iter()
+                .map(|column| row.remove(column).unwrap_or(Value::Null))
+                .collect()
+        })
+        .collect();
+
+    Table {
+        name: table_name(kind),
+        columns,
+        rows,
+        assets,
+    }
+}
+
+/// Plural name of a kind, used for file and sheet names.
+fn table_name(kind: &str) -> String {
+    match kind {
+        "gear" | "glasses" | "other" => kind.to_string(),
+        "sentry" => "sentries".to_string(),
+        _ => format!("{}s", kind),
+    }
+}
+
+/// Text of a cell, lists are written as JSON.
+pub fn cell_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
@@ -0,0 +1,33 @@
+//! Running the CLI on the fixtures of the parser, shared by the integration tests.
+
+// Each test uses only some of these.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Folder of a parser fixture, such as `Load_Order/vanilla`.
+pub fn fixture_dir(fixture: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../torture-parser/tests/fixtures")
+        .join(fixture)
+}
+
+/// Runs the CLI with `args`, and checks that it succeeds.
+pub fn run<I, S>(args: I) -> Output
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let output = Command::new(env!("CARGO_BIN_EXE_torture-cli"))
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    output
+}
@@ -0,0 +1,36 @@
+//! Showing assets of the parser fixtures with the `show` subcommand.
+
+mod common;
+
+use common::{fixture_dir, run};
+
+fn show(fixture: &str, args: &[&str]) -> String {
+    let path = fixture_dir(fixture);
+    let mut command = vec!["--path", path.to_str().unwrap(), "show"];
+    command.extend(args);
+
+    String::from_utf8(run(command).stdout).unwrap()
+}
+
+#[test]
+fn bare_id_prefers_items() {
+    // The Muzzle effect shares ID 363 with the Maplestrike.
+    let output = show("Load_Order/vanilla", &["363"]);
+
+    assert!(output.starts_with("gun 363 (Maplestrike)\n"), "{}", output);
+}
+
+#[test]
+fn magazine_replacements_are_named() {
+    let output = show(
+        ".",
+        &["363", "--file", "fixtures/Maplestrike/Maplestrike.dat"],
+    );
+
+    assert!(
+        output.contains("\nMagazine replacements\n  Russia: 1165 ("),
+        "{}",
+        output
+    );
+    assert!(!output.contains("magazine_replacements"), "{}", output);
+}
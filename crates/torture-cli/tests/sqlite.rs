@@ -0,0 +1,49 @@
+//! Exporting `tests/fixtures/Load_Order/vanilla` of the parser to SQLite with `export --sqlite`.
+
+#![cfg(feature = "sqlite")]
+
+mod common;
+
+use std::path::Path;
+
+use rusqlite::Connection;
+
+use common::{fixture_dir, run};
+
+fn export(database: &Path) -> Connection {
+    let _ = std::fs::remove_file(database);
+
+    let vanilla = fixture_dir("Load_Order/vanilla");
+    run([
+        "--path".as_ref(),
+        vanilla.as_os_str(),
+        "export".as_ref(),
+        "--sqlite".as_ref(),
+        database.as_os_str(),
+    ]);
+
+    Connection::open(database).unwrap()
+}
+
+#[test]
+fn localization_links_to_assets() {
+    let database = Path::new(env!("CARGO_TARGET_TMPDIR")).join("localization.sqlite");
+    let connection = export(&database);
+
+    let mut statement = connection
+        .prepare(
+            "SELECT localization.name, localization.language, localization.description
+             FROM localization JOIN assets USING (asset_id)
+             ORDER BY localization.name",
+        )
+        .unwrap();
+    let rows: Vec<(String, String, String)> = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+
+    // Each asset has the name and description of its `English.dat`.
+    let row = |name: &str| (name.to_string(), "English".to_string(), name.to_string());
+    assert_eq!(rows, [row("Bandana"), row("Maplestrike"), row("Muzzle")]);
+}
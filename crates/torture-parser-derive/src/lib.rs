@@ -0,0 +1,704 @@
+//! `#[derive(DatAsset)]`, which generates the `.dat` parser, writer and schema of an asset
+//! from its struct definition.
+//!
+//! Every field declares how it maps to the `.dat` file:
+//!
+//! - `#[dat(key = "Player_Damage")]` reads the value of a key.
+//! - `#[dat(key = "Player_Damage", default = 1.0)]` also sets the value when the key is
+//!   missing. The default can refer to other fields through `item`, e.g.
+//!   `default = item.resource_damage`.
+//! - `#[dat(key = "Laser_Color", legacy_rgb)]` also reads a color from the legacy
+//!   `_R`, `_G` and `_B` keys.
+//! - `#[dat(key = "Explosive", flag)]` sets a `bool` when the key is present.
+//! - `#[dat(flatten)]` parses the field as an asset of its own, such as a parent asset.
+//! - `#[dat(skip)]` leaves the field to the struct's `finish` function.
+//! - `#[dat(skip, schema = SpotLightConfig)]` also adds the keys of the asset `finish` reads
+//!   the field as to the schema.
+//!
+//! Parsing logic that does not fit these is done by functions given on the struct:
+//!
+//! - `#[dat(finish = path)]` is called with
+//!   `(&mut Self, &dyn AssetSource, &Path, &str, &mut Vec<Diagnostic>)` once every key is read,
+//!   before defaults are applied.
+//! - `#[dat(write = path)]` is called with `(&Self, &mut String)` once every key is written.
+//! - `#[dat(keys = ["Calibers", "Caliber_#"])]` lists the keys `finish` reads, with indices
+//!   written as `#`, so they are part of the schema.
+//! - `#[dat(ignore = ["Size_X"])]` lists keys the game reads that the asset does not model.
+//!
+//! `#[derive(DatValue)]` reads and writes an enum without fields by its variant names, see
+//! [`derive_dat_value`].
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, Ident, LitStr, Path, Type};
+
+#[proc_macro_derive(DatAsset, attributes(dat))]
+pub fn derive_dat_asset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `FromDatValue` and `ToDatValue` for an enum without fields.
+///
+/// Variants are written with their words separated by underscores, so `ArrestStart` is
+/// `Arrest_Start`. Reading ignores case and underscores, and reports any other value as an
+/// `UnknownValue`.
+#[proc_macro_derive(DatValue)]
+pub fn derive_dat_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_value(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Kind {
+    Value {
+        key: LitStr,
+        default: Option<Box<DefaultValue>>,
+        legacy_rgb: bool,
+    },
+    Flag {
+        key: LitStr,
+    },
+    Flatten,
+    Skip {
+        schema: Option<Box<Type>>,
+    },
+}
+
+/// The expression of `default = ...`, and its text for the schema.
+struct DefaultValue {
+    expr: Expr,
+    text: String,
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    kind: Kind,
+    description: String,
+}
+
+#[derive(Default)]
+struct Options {
+    finish: Option<Path>,
+    write: Option<Path>,
+    keys: Vec<LitStr>,
+    ignore: Vec<LitStr>,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let options = parse_options(&input)?;
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "DatAsset can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "DatAsset can only be derived for structs with named fields",
+        ));
+    };
+
+    let fields = named
+        .named
+        .iter()
+        .map(parse_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+    check_duplicate_keys(&fields)?;
+
+    let (parser, read_dat) = expand_parser(name, &fields, &options);
+    let defaults = expand_defaults(name, &fields);
+    let asset = expand_asset(name, &fields, &options, read_dat);
+
+    Ok(quote! {
+        #parser
+        #defaults
+        #asset
+    })
+}
+
+fn parse_options(input: &DeriveInput) -> syn::Result<Options> {
+    let mut options = Options::default();
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("dat"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("finish") {
+                options.finish = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("write") {
+                options.write = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("keys") {
+                options.keys.extend(parse_keys(meta.value()?)?);
+            } else if meta.path.is_ident("ignore") {
+                options.ignore.extend(parse_keys(meta.value()?)?);
+            } else {
+                return Err(meta.error("expected `finish`, `write`, `keys` or `ignore`"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    if let (None, Some(key)) = (&options.finish, options.keys.first()) {
+        return Err(syn::Error::new(
+            key.span(),
+            "`keys` are read by the `finish` function, which is missing",
+        ));
+    }
+
+    Ok(options)
+}
+
+/// Parses `["Key", ...]`.
+fn parse_keys(input: syn::parse::ParseStream) -> syn::Result<Vec<LitStr>> {
+    let content;
+    syn::bracketed!(content in input);
+    let keys = content.parse_terminated(<LitStr as syn::parse::Parse>::parse, syn::Token![,])?;
+
+    Ok(keys.into_iter().collect())
+}
+
+fn parse_field(field: &syn::Field) -> syn::Result<Field> {
+    let ident = field.ident.clone().expect("named fields have an ident");
+
+    let mut key: Option<LitStr> = None;
+    let mut default: Option<Box<DefaultValue>> = None;
+    let mut legacy_rgb = false;
+    let mut flag = false;
+    let mut flatten = false;
+    let mut skip = false;
+    let mut schema: Option<Box<Type>> = None;
+    let mut found = false;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("dat"))
+    {
+        found = true;
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("key") {
+                key = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("default") {
+                let expr: Expr = meta.value()?.parse()?;
+                let text = pretty(&expr);
+                default = Some(Box::new(DefaultValue { expr, text }));
+            } else if meta.path.is_ident("legacy_rgb") {
+                legacy_rgb = true;
+            } else if meta.path.is_ident("flag") {
+                flag = true;
+            } else if meta.path.is_ident("flatten") {
+                flatten = true;
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+            } else if meta.path.is_ident("schema") {
+                schema = Some(Box::new(meta.value()?.parse()?));
+            } else {
+                return Err(meta.error(
+                    "expected `key`, `default`, `legacy_rgb`, `flag`, `flatten`, `skip` or `schema`",
+                ));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    if !found {
+        return Err(syn::Error::new(
+            field.span(),
+            "field needs #[dat(key = \"...\")], #[dat(flatten)] or #[dat(skip)]",
+        ));
+    }
+
+    let kind = match (key, flatten, skip) {
+        (Some(key), false, false) if flag => {
+            if default.is_some() || legacy_rgb {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "flags cannot have a `default` or `legacy_rgb`",
+                ));
+            }
+
+            Kind::Flag { key }
+        }
+        (Some(key), false, false) => Kind::Value {
+            key,
+            default,
+            legacy_rgb,
+        },
+        (None, true, false) => Kind::Flatten,
+        (None, false, true) => Kind::Skip { schema },
+        _ => {
+            return Err(syn::Error::new(
+                field.span(),
+                "field needs exactly one of `key`, `flatten` or `skip`",
+            ))
+        }
+    };
+
+    Ok(Field {
+        ident,
+        ty: field.ty.clone(),
+        kind,
+        description: doc_comment(&field.attrs),
+    })
+}
+
+fn check_duplicate_keys(fields: &[Field]) -> syn::Result<()> {
+    let mut seen: Vec<String> = Vec::new();
+
+    for field in fields {
+        let key = match &field.kind {
+            Kind::Value { key, .. } | Kind::Flag { key } => key,
+            Kind::Flatten | Kind::Skip { .. } => continue,
+        };
+
+        if seen.contains(&key.value()) {
+            return Err(syn::Error::new(
+                key.span(),
+                format!("`{}` is already read by another field", key.value()),
+            ));
+        }
+        seen.push(key.value());
+    }
+
+    Ok(())
+}
+
+/// Joins the `///` lines of a field, keeping paragraphs apart.
+fn doc_comment(attrs: &[syn::Attribute]) -> String {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(doc),
+                        ..
+                    }),
+                ..
+            }) => Some(doc.value().trim().to_string()),
+            _ => None,
+        })
+        .collect();
+
+    lines.join("\n").trim().to_string()
+}
+
+/// Formats tokens the way they were most likely written, e.g. `Vec<u16>` instead of `Vec < u16 >`.
+fn pretty(tokens: impl ToTokens) -> String {
+    tokens
+        .to_token_stream()
+        .to_string()
+        .replace(" . ", ".")
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace(" >", ">")
+        .replace(" (", "(")
+        .replace("( ", "(")
+        .replace(" )", ")")
+        .replace(" ,", ",")
+}
+
+fn legacy_rgb_keys(key: &LitStr) -> [String; 3] {
+    ["R", "G", "B"].map(|channel| format!("{}_{}", key.value(), channel))
+}
+
+/// Generates the `Parser` impl and the `read_dat` function of the `DatAsset` impl.
+fn expand_parser(
+    name: &Ident,
+    fields: &[Field],
+    options: &Options,
+) -> (TokenStream2, TokenStream2) {
+    let mut inits = Vec::new();
+    let mut legacy = Vec::new();
+    let mut arms = Vec::new();
+    let mut reads_values = false;
+
+    for Field {
+        ident, ty, kind, ..
+    } in fields
+    {
+        match kind {
+            Kind::Flatten => inits.push(quote! {
+                #ident: <#ty as ::torture_parser::parser::dat::DatAsset>::read_dat(
+                    source,
+                    directory,
+                    content,
+                    diagnostics,
+                )?,
+            }),
+            Kind::Value {
+                key, legacy_rgb, ..
+            } => {
+                reads_values = true;
+                arms.push(quote! {
+                    #key => {
+                        if let Some(value) =
+                            ::torture_parser::parser::dat::read_or_report(field, value, diagnostics)?
+                        {
+                            item.#ident = value;
+                        }
+                    }
+                });
+
+                if *legacy_rgb {
+                    legacy.push(quote! {
+                        if let Some(color) =
+                            ::torture_parser::parser::color::Color::parse_legacy_rgb(#key, content)?
+                        {
+                            item.#ident = color;
+                        }
+                    });
+                }
+            }
+            Kind::Flag { key } => arms.push(quote! {
+                #key => item.#ident = true,
+            }),
+            Kind::Skip { .. } => {}
+        }
+    }
+
+    let value = if reads_values {
+        quote!(value)
+    } else {
+        quote!(_)
+    };
+    let read = (!arms.is_empty()).then(|| {
+        quote! {
+            for (field, #value) in ::torture_parser::parser::dat::lines(content) {
+                match field {
+                    #(#arms)*
+                    _ => {}
+                }
+            }
+        }
+    });
+    let finish = options.finish.as_ref().map(|finish| {
+        quote! {
+            #finish(&mut item, source, directory, content, diagnostics)?;
+        }
+    });
+
+    let parser = quote! {
+        impl ::torture_parser::parser::Parser<#name> for #name {
+            fn parse<P: ::std::convert::AsRef<::std::path::Path> + ?::std::marker::Sized>(
+                directory: &P,
+                content: &str,
+            ) -> ::anyhow::Result<#name> {
+                <#name as ::torture_parser::parser::dat::DatAsset>::parse_with_diagnostics(
+                    directory, content,
+                )
+                .map(|(item, _)| item)
+            }
+        }
+    };
+    let uses_directory = !inits.is_empty() || finish.is_some();
+    let (source, directory) = if uses_directory {
+        (quote!(source), quote!(directory))
+    } else {
+        (quote!(_source), quote!(_directory))
+    };
+    let diagnostics = if uses_directory || reads_values {
+        quote!(diagnostics)
+    } else {
+        quote!(_diagnostics)
+    };
+    let read_dat = quote! {
+        fn read_dat(
+            #source: &dyn ::torture_parser::source::AssetSource,
+            #directory: &::std::path::Path,
+            content: &str,
+            #diagnostics: &mut ::std::vec::Vec<::torture_parser::parser::dat::Diagnostic>,
+        ) -> ::anyhow::Result<#name> {
+            #[allow(clippy::needless_update)]
+            let mut item = #name {
+                #(#inits)*
+                ..::std::default::Default::default()
+            };
+
+            #(#legacy)*
+            #read
+            #finish
+
+            ::torture_parser::parser::defaults::Defaults::apply_defaults(&mut item, content);
+
+            Ok(item)
+        }
+    };
+
+    (parser, read_dat)
+}
+
+fn expand_defaults(name: &Ident, fields: &[Field]) -> TokenStream2 {
+    let defaults: Vec<TokenStream2> = fields
+        .iter()
+        .filter_map(|Field { ident, ty, kind, .. }| match kind {
+            Kind::Value {
+                key,
+                default: Some(default),
+                legacy_rgb,
+            } => {
+                // A value that could not be read is reported and left at the default too.
+                let legacy_keys = if *legacy_rgb {
+                    legacy_rgb_keys(key).to_vec()
+                } else {
+                    Vec::new()
+                };
+
+                let default = &default.expr;
+                Some(quote! {
+                    if !(::torture_parser::parser::defaults::has_value::<#ty>(content, #key)
+                        #(|| ::torture_parser::parser::defaults::has_key(content, #legacy_keys))*)
+                    {
+                        item.#ident = #default;
+                    }
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    let body = if defaults.is_empty() {
+        quote! {
+            let _ = content;
+        }
+    } else {
+        quote! {
+            let item = self;
+            #(#defaults)*
+        }
+    };
+
+    quote! {
+        impl ::torture_parser::parser::defaults::Defaults for #name {
+            fn apply_defaults(&mut self, content: &str) {
+                #body
+            }
+        }
+    }
+}
+
+fn expand_asset(
+    name: &Ident,
+    fields: &[Field],
+    options: &Options,
+    read_dat: TokenStream2,
+) -> TokenStream2 {
+    let mut schema = Vec::new();
+    let mut writes = Vec::new();
+
+    for Field {
+        ident,
+        ty,
+        kind,
+        description,
+    } in fields
+    {
+        match kind {
+            Kind::Flatten => {
+                schema.push(quote! {
+                    fields.extend(<#ty as ::torture_parser::parser::dat::DatAsset>::schema());
+                });
+                writes.push(quote! {
+                    ::torture_parser::parser::dat::DatAsset::write_dat(&self.#ident, out);
+                });
+            }
+            Kind::Value {
+                key,
+                default,
+                legacy_rgb: _,
+            } => {
+                let value_type = pretty(ty);
+                let default = match default {
+                    Some(default) => {
+                        let text = &default.text;
+                        quote!(Some(#text))
+                    }
+                    None => quote!(None),
+                };
+
+                schema.push(quote! {
+                    fields.push(::torture_parser::parser::dat::DatField {
+                        key: #key,
+                        kind: ::torture_parser::parser::dat::DatFieldKind::Value,
+                        value_type: #value_type,
+                        default: #default,
+                        description: #description,
+                    });
+                });
+                writes.push(quote! {
+                    ::torture_parser::parser::dat::write_value(out, #key, &self.#ident);
+                });
+            }
+            Kind::Flag { key } => {
+                schema.push(quote! {
+                    fields.push(::torture_parser::parser::dat::DatField {
+                        key: #key,
+                        kind: ::torture_parser::parser::dat::DatFieldKind::Flag,
+                        value_type: "bool",
+                        default: None,
+                        description: #description,
+                    });
+                });
+                writes.push(quote! {
+                    if self.#ident {
+                        ::torture_parser::parser::dat::write_flag(out, #key);
+                    }
+                });
+            }
+            Kind::Skip { schema: Some(ty) } => {
+                schema.push(quote! {
+                    fields.extend(<#ty as ::torture_parser::parser::dat::DatAsset>::schema());
+                });
+            }
+            Kind::Skip { schema: None } => {}
+        }
+    }
+
+    if let Some(finish) = &options.finish {
+        let description = format!("Read by `{}`.", pretty(finish));
+        for key in &options.keys {
+            schema.push(quote! {
+                fields.push(::torture_parser::parser::dat::DatField {
+                    key: #key,
+                    kind: ::torture_parser::parser::dat::DatFieldKind::Finish,
+                    value_type: "",
+                    default: None,
+                    description: #description,
+                });
+            });
+        }
+    }
+    for key in &options.ignore {
+        schema.push(quote! {
+            fields.push(::torture_parser::parser::dat::DatField {
+                key: #key,
+                kind: ::torture_parser::parser::dat::DatFieldKind::Ignored,
+                value_type: "",
+                default: None,
+                description: "Read by the game, but not by the parser.",
+            });
+        });
+    }
+
+    let write = options.write.as_ref().map(|write| {
+        quote! {
+            #write(self, out);
+        }
+    });
+    let out = if writes.is_empty() && write.is_none() {
+        quote!(_out)
+    } else {
+        quote!(out)
+    };
+
+    quote! {
+        impl ::torture_parser::parser::dat::DatAsset for #name {
+            #read_dat
+
+            fn schema() -> ::std::vec::Vec<::torture_parser::parser::dat::DatField> {
+                #[allow(unused_mut)]
+                let mut fields = ::std::vec::Vec::new();
+                #(#schema)*
+                fields
+            }
+
+            fn write_dat(&self, #out: &mut ::std::string::String) {
+                #(#writes)*
+                #write
+            }
+        }
+    }
+}
+
+fn expand_value(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "DatValue can only be derived for enums",
+        ));
+    };
+
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    let mut keys = Vec::new();
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.span(),
+                "DatValue can only be derived for enums without fields",
+            ));
+        }
+
+        let ident = &variant.ident;
+        let key = variant_key(&ident.to_string());
+        let normalized = ident.to_string().to_ascii_lowercase();
+
+        reads.push(quote!(#normalized => Ok(Self::#ident),));
+        writes.push(quote!(Self::#ident => #key,));
+        keys.push(key);
+    }
+
+    let type_name = name.to_string();
+    let expected = keys.join(", ");
+
+    Ok(quote! {
+        impl ::torture_parser::parser::dat::FromDatValue for #name {
+            fn from_dat_value(value: &str) -> ::anyhow::Result<Self> {
+                let value = value.split_whitespace().next().unwrap_or("");
+
+                match value.replace('_', "").to_ascii_lowercase().as_str() {
+                    #(#reads)*
+                    _ => Err(::torture_parser::parser::dat::UnknownValue(format!(
+                        "Unknown {} '{}', expected one of {}",
+                        #type_name, value, #expected,
+                    ))
+                    .into()),
+                }
+            }
+        }
+
+        impl ::torture_parser::parser::dat::ToDatValue for #name {
+            fn to_dat_value(&self) -> ::std::string::String {
+                match self {
+                    #(#writes)*
+                }
+                .to_string()
+            }
+        }
+    })
+}
+
+/// Turns a variant name such as `ArrestStart` into its key, `Arrest_Start`.
+fn variant_key(variant: &str) -> String {
+    let mut key = String::with_capacity(variant.len() + 2);
+
+    for (index, character) in variant.char_indices() {
+        if index > 0 && character.is_ascii_uppercase() {
+            key.push('_');
+        }
+        key.push(character);
+    }
+
+    key
+}
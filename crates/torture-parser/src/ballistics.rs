@@ -0,0 +1,166 @@
+//! Bullet drop simulation, following how Unturned moves bullets fired by guns.
+//!
+//! Each step, a bullet moves `Ballistic_Travel` meters along its direction, after which
+//! `Ballistic_Drop` (multiplied by the barrel's `Ballistic_Drop`) is subtracted from the
+//! vertical component of the direction, which is then normalized again.
+//! The bullet despawns after `Ballistic_Steps` steps.
+//!
+//! Barrels are the only attachments that change this path. Magazines and sights have no
+//! ballistic properties in the game, though a sight's distance markers can be checked against
+//! the simulated path with [`BallisticsSimulation::markers`].
+
+use crate::parser::assets::barrel::ItemBarrelAsset;
+use crate::parser::assets::gun::ItemGunAsset;
+use crate::parser::assets::sight::{DistanceMarker, ItemSightAsset, Side};
+
+/// Seconds between two simulation steps.
+pub const STEP_DURATION: f32 = 0.02;
+
+/// State of a bullet after a simulation step.
+#[derive(Debug, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BallisticsPoint {
+    /// Horizontal distance from the muzzle, in meters.
+    pub distance: f32,
+
+    /// Meters the bullet has fallen below the line of sight.
+    pub drop: f32,
+
+    /// Angle between the line of sight and the bullet, in degrees.
+    ///
+    /// This is how far above the target one has to aim, regardless of zoom.
+    pub angle: f32,
+
+    /// Seconds since the bullet was fired.
+    pub time: f32,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct BallisticsSimulation {
+    /// One point per step, starting at the muzzle.
+    pub points: Vec<BallisticsPoint>,
+}
+
+impl BallisticsSimulation {
+    /// Simulates a bullet fired horizontally from `gun`, with an optional barrel attached, whose
+    /// `Ballistic_Drop` multiplies the gun's.
+    pub fn simulate(gun: &ItemGunAsset, barrel: Option<&ItemBarrelAsset>) -> BallisticsSimulation {
+        let ballistics = &gun.ballistics;
+        let drop_multiplier = barrel.map_or(1.0, |barrel| barrel.ballistic_drop);
+        let gravity = ballistics.drop * drop_multiplier;
+
+        let mut points = Vec::with_capacity(ballistics.steps as usize + 1);
+        points.push(BallisticsPoint::default());
+
+        let (mut x, mut y) = (0.0f32, 0.0f32);
+        let (mut direction_x, mut direction_y) = (1.0f32, 0.0f32);
+
+        for step in 1..=ballistics.steps {
+            x += direction_x * ballistics.travel;
+            y += direction_y * ballistics.travel;
+
+            direction_y -= gravity;
+            let length = direction_x.hypot(direction_y);
+            direction_x /= length;
+            direction_y /= length;
+
+            points.push(BallisticsPoint {
+                distance: x,
+                drop: -y,
+                angle: (-y).atan2(x).to_degrees(),
+                time: step as f32 * STEP_DURATION,
+            });
+        }
+
+        BallisticsSimulation { points }
+    }
+
+    /// Horizontal distance covered before the bullet despawns, past which nothing is hit.
+    pub fn max_range(&self) -> f32 {
+        self.points.last().map_or(0.0, |point| point.distance)
+    }
+
+    /// Farthest distance at which the bullet has fallen at most `max_drop` meters, such as the
+    /// height of a head, so aiming at the target hits it without holding over.
+    ///
+    /// This is the [`max_range`](Self::max_range) when the bullet despawns before falling that
+    /// far.
+    pub fn effective_range(&self, max_drop: f32) -> f32 {
+        // The bullet only ever falls further along its path.
+        let Some(index) = self.points.iter().position(|point| point.drop > max_drop) else {
+            return self.max_range();
+        };
+        if index == 0 {
+            return 0.0;
+        }
+
+        let (from, to) = (self.points[index - 1], self.points[index]);
+        let t = (max_drop - from.drop) / (to.drop - from.drop);
+
+        from.distance + (to.distance - from.distance) * t
+    }
+
+    /// The bullet's state at each distance marker of `sight`, or `None` for markers past the
+    /// point where it despawns.
+    pub fn markers(&self, sight: &ItemSightAsset) -> Vec<(f32, Option<BallisticsPoint>)> {
+        sight
+            .distance_markers
+            .iter()
+            .map(|marker| (marker.distance, self.at(marker.distance)))
+            .collect()
+    }
+
+    /// Interpolates the bullet's state at `distance`, or `None` if it despawns before reaching it.
+    pub fn at(&self, distance: f32) -> Option<BallisticsPoint> {
+        let index = self
+            .points
+            .iter()
+            .position(|point| point.distance >= distance)?;
+
+        if index == 0 {
+            return self.points.first().copied();
+        }
+
+        let (from, to) = (self.points[index - 1], self.points[index]);
+        let t = (distance - from.distance) / (to.distance - from.distance);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        Some(BallisticsPoint {
+            distance,
+            drop: lerp(from.drop, to.drop),
+            angle: lerp(from.angle, to.angle),
+            time: lerp(from.time, to.time),
+        })
+    }
+
+    /// Drop table with a row every `interval` meters, up to the maximum range.
+    pub fn table(&self, interval: f32) -> Vec<BallisticsPoint> {
+        if interval <= 0.0 {
+            return Vec::new();
+        }
+
+        (1..)
+            .map(|index| index as f32 * interval)
+            .map_while(|distance| self.at(distance))
+            .collect()
+    }
+
+    /// Distance markers every `interval` meters, for use in a sight's `Distance_Markers`.
+    ///
+    /// The game positions markers vertically by itself, so only distances within range are
+    /// generated, alternating sides to keep labels readable.
+    pub fn distance_markers(&self, interval: f32) -> Vec<DistanceMarker> {
+        self.table(interval)
+            .into_iter()
+            .enumerate()
+            .map(|(index, point)| DistanceMarker {
+                distance: point.distance,
+                side: if index % 2 == 0 {
+                    Side::Right
+                } else {
+                    Side::Left
+                },
+                ..Default::default()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,349 @@
+//! Damage per hit, shots-to-kill and time-to-kill of weapons against players, zombies,
+//! animals and buildables.
+
+use crate::parser::assets::caliber::ItemCaliberAsset;
+use crate::parser::assets::clothing::ItemClothingAsset;
+use crate::parser::assets::gun::{DamageFalloff, ItemGunAsset};
+use crate::parser::assets::magazine::ItemMagazineAsset;
+use crate::parser::assets::melee::ItemMeleeAsset;
+use crate::parser::assets::throwable::ItemThrowableAsset;
+use crate::parser::assets::weapon::{AnimalDamage, ItemWeaponAsset, PlayerDamage, ZombieDamage};
+use crate::raid::Buildable;
+
+/// Seconds per simulation tick, which is what `Firerate` is counted in.
+pub const TICK_DURATION: f32 = 0.02;
+
+/// Health of a player.
+pub const PLAYER_HEALTH: u16 = 100;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum HitLocation {
+    Skull,
+    Spine,
+    Arm,
+    Leg,
+}
+
+/// Where players and zombies can be hit.
+const BODY: [HitLocation; 4] = [
+    HitLocation::Skull,
+    HitLocation::Spine,
+    HitLocation::Arm,
+    HitLocation::Leg,
+];
+
+/// Where animals can be hit, as they have no arms.
+const ANIMAL_BODY: [HitLocation; 3] = [HitLocation::Skull, HitLocation::Spine, HitLocation::Leg];
+
+impl HitLocation {
+    /// Picks the multiplier of this location out of a weapon's `[skull, spine, arm, leg]`.
+    fn pick(self, [skull, spine, arm, leg]: [f32; 4]) -> f32 {
+        match self {
+            HitLocation::Skull => skull,
+            HitLocation::Spine => spine,
+            HitLocation::Arm => arm,
+            HitLocation::Leg => leg,
+        }
+    }
+}
+
+/// Damage of a weapon against one kind of target, and its multipliers by hit location.
+trait LocationDamage {
+    fn amount(&self) -> f32;
+
+    /// Multipliers of the skull, spine, arms and legs.
+    fn multipliers(&self) -> [f32; 4];
+}
+
+impl LocationDamage for PlayerDamage {
+    fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    fn multipliers(&self) -> [f32; 4] {
+        [
+            self.skull_multiplier,
+            self.spine_multiplier,
+            self.arm_multiplier,
+            self.leg_multiplier,
+        ]
+    }
+}
+
+impl LocationDamage for ZombieDamage {
+    fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    fn multipliers(&self) -> [f32; 4] {
+        [
+            self.skull_multiplier,
+            self.spine_multiplier,
+            self.arm_multiplier,
+            self.leg_multiplier,
+        ]
+    }
+}
+
+impl LocationDamage for AnimalDamage {
+    fn amount(&self) -> f32 {
+        self.amount
+    }
+
+    /// Animals have no arms, which count as the spine.
+    fn multipliers(&self) -> [f32; 4] {
+        [
+            self.skull_multiplier,
+            self.spine_multiplier,
+            self.spine_multiplier,
+            self.leg_multiplier,
+        ]
+    }
+}
+
+/// Anything that deals damage, with its attachments already taken into account.
+#[derive(Debug)]
+pub struct DamageSource<'a> {
+    pub weapon: &'a ItemWeaponAsset,
+
+    /// Product of every damage multiplier, such as attachment `Ballistic_Damage_Multiplier`s.
+    pub multiplier: f32,
+
+    /// Number of projectiles per shot, all of which are assumed to hit.
+    pub pellets: u8,
+
+    /// Ticks between two shots, if the weapon fires repeatedly.
+    pub firerate: Option<u8>,
+
+    /// Damage falloff of guns, applied when a distance is given.
+    pub falloff: Option<&'a DamageFalloff>,
+
+    /// If the damage is caused by an explosion, which can damage buildables without `Vulnerable`,
+    /// and is reduced by `Armor_Explosion` instead of `Armor`.
+    pub is_explosive: bool,
+}
+
+/// Multipliers servers apply to damage against buildables depending on their `Armor_Tier`.
+///
+/// These come from the `Barricades` and `Structures` sections of the server config.
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ArmorTierMultipliers {
+    pub low_tier: f32,
+    pub high_tier: f32,
+}
+
+/// Clothing worn by a player, each piece protecting its own body parts.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct PlayerArmor<'a> {
+    /// Protects the skull.
+    pub hat: Option<&'a ItemClothingAsset>,
+
+    /// Protects the spine, on top of the shirt.
+    pub vest: Option<&'a ItemClothingAsset>,
+
+    /// Protects the spine and arms.
+    pub shirt: Option<&'a ItemClothingAsset>,
+
+    /// Protects the legs.
+    pub pants: Option<&'a ItemClothingAsset>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum Target<'a> {
+    Player(PlayerArmor<'a>),
+    Zombie { health: u16 },
+    Animal { health: u16 },
+    Buildable(Buildable<'a>, ArmorTierMultipliers),
+}
+
+#[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HitDamage {
+    /// Body part that was hit, `None` for buildables.
+    pub location: Option<HitLocation>,
+
+    /// Damage dealt by one shot, with every pellet hitting.
+    pub damage: f32,
+
+    /// Number of shots needed, or `None` if the target cannot be damaged.
+    pub shots_to_kill: Option<u32>,
+
+    /// Seconds between the first and the killing shot, if the weapon fires repeatedly.
+    pub time_to_kill: Option<f32>,
+}
+
+impl Default for ArmorTierMultipliers {
+    fn default() -> Self {
+        Self {
+            low_tier: 1.0,
+            high_tier: 0.5,
+        }
+    }
+}
+
+impl<'a> DamageSource<'a> {
+    /// A gun with its attachments, and the magazine loaded into it.
+    ///
+    /// Each attachment's damage multiplier is applied, and the attachments' `Firerate`
+    /// is subtracted from the gun's.
+    pub fn gun(
+        gun: &'a ItemGunAsset,
+        attachments: &[&ItemCaliberAsset],
+        magazine: Option<&ItemMagazineAsset>,
+    ) -> DamageSource<'a> {
+        let mut calibers: Vec<&ItemCaliberAsset> = attachments.to_vec();
+        calibers.extend(magazine.map(|magazine| &magazine.item_caliber_asset));
+
+        let multiplier = calibers
+            .iter()
+            .map(|caliber| caliber.ballistic_damage_multiplier)
+            .product();
+        let firerate = calibers
+            .iter()
+            .fold(gun.firerate as i32, |firerate, caliber| {
+                firerate - caliber.firerate as i32
+            });
+
+        DamageSource {
+            weapon: &gun.item_weapon_asset,
+            multiplier,
+            pellets: magazine.map_or(1, |magazine| magazine.pellets.max(1)),
+            firerate: Some(firerate.clamp(0, u8::MAX as i32) as u8),
+            falloff: Some(&gun.damage_falloff),
+            is_explosive: false,
+        }
+    }
+
+    /// A melee weapon's regular (“Primary”) attack.
+    pub fn melee(melee: &'a ItemMeleeAsset) -> DamageSource<'a> {
+        DamageSource::weapon(&melee.item_weapon_asset)
+    }
+
+    /// A throwable, which is only an explosion if it is `Explosive`.
+    pub fn throwable(throwable: &'a ItemThrowableAsset) -> DamageSource<'a> {
+        DamageSource {
+            is_explosive: throwable.is_explosive,
+            ..DamageSource::weapon(&throwable.item_weapon_asset)
+        }
+    }
+
+    /// Any other weapon, hitting once with no modifiers.
+    pub fn weapon(weapon: &'a ItemWeaponAsset) -> DamageSource<'a> {
+        DamageSource {
+            weapon,
+            multiplier: 1.0,
+            pellets: 1,
+            firerate: None,
+            falloff: None,
+            is_explosive: false,
+        }
+    }
+
+    /// Multiplier from damage falloff at `distance` meters.
+    pub fn falloff_multiplier(&self, distance: f32) -> f32 {
+        let Some(falloff) = self.falloff else {
+            return 1.0;
+        };
+
+        let range = self.weapon.range;
+        let start = falloff.range * range;
+        let end = falloff.max_range * range;
+
+        if distance <= start || end <= start {
+            return 1.0;
+        }
+
+        let t = ((distance - start) / (end - start)).min(1.0);
+        1.0 + (falloff.multiplier - 1.0) * t
+    }
+
+    /// Damage for every hit location of `target`, at `distance` meters.
+    pub fn against(&self, target: &Target, distance: f32) -> Vec<HitDamage> {
+        let times = self.multiplier * self.pellets as f32 * self.falloff_multiplier(distance);
+        let weapon = self.weapon;
+
+        match target {
+            Target::Player(armor) => self.hits(
+                &weapon.player_damage,
+                &BODY,
+                PLAYER_HEALTH,
+                times,
+                |location| armor.multiplier(location, self.is_explosive),
+            ),
+            Target::Zombie { health } => {
+                self.hits(&weapon.zombie_damage, &BODY, *health, times, |_| 1.0)
+            }
+            Target::Animal { health } => {
+                self.hits(&weapon.animal_damage, &ANIMAL_BODY, *health, times, |_| 1.0)
+            }
+            Target::Buildable(buildable, tiers) => {
+                let damage = buildable.damage_taken(
+                    weapon.barricade_damage * times,
+                    weapon.structure_damage * times,
+                    self.is_explosive,
+                    *tiers,
+                );
+
+                vec![self.hit(None, damage, buildable.health())]
+            }
+        }
+    }
+
+    /// Damage at each of `locations`, times the multiplier of the location and of the armor
+    /// worn there.
+    fn hits(
+        &self,
+        damage: &impl LocationDamage,
+        locations: &[HitLocation],
+        health: u16,
+        times: f32,
+        armor: impl Fn(HitLocation) -> f32,
+    ) -> Vec<HitDamage> {
+        locations
+            .iter()
+            .map(|&location| {
+                let multiplier = location.pick(damage.multipliers());
+                let damage = damage.amount() * multiplier * armor(location) * times;
+
+                self.hit(Some(location), damage, health)
+            })
+            .collect()
+    }
+
+    fn hit(&self, location: Option<HitLocation>, damage: f32, health: u16) -> HitDamage {
+        // The game truncates damage to whole numbers before applying it.
+        let damage = damage.floor().max(0.0);
+        let shots_to_kill = (damage > 0.0).then(|| (health as f32 / damage).ceil() as u32);
+        let time_to_kill = shots_to_kill.zip(self.firerate).map(|(shots, firerate)| {
+            shots.saturating_sub(1) as f32 * firerate as f32 * TICK_DURATION
+        });
+
+        HitDamage {
+            location,
+            damage,
+            shots_to_kill,
+            time_to_kill,
+        }
+    }
+}
+
+impl PlayerArmor<'_> {
+    /// Multiplier on damage taken at `location`.
+    pub fn multiplier(&self, location: HitLocation, is_explosive: bool) -> f32 {
+        let armor = |clothing: Option<&ItemClothingAsset>| {
+            clothing.map_or(1.0, |clothing| {
+                if is_explosive {
+                    clothing.armor_explosion
+                } else {
+                    clothing.armor
+                }
+            })
+        };
+
+        match location {
+            HitLocation::Skull => armor(self.hat),
+            HitLocation::Spine => armor(self.vest) * armor(self.shirt),
+            HitLocation::Arm => armor(self.shirt),
+            HitLocation::Leg => armor(self.pants),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+//! Comparing two sets of assets, such as two game versions or two versions of a mod.
+//!
+//! Assets are matched by GUID, or by legacy ID within the same [`AssetCategory`] when either
+//! of them has no GUID. Matched assets are compared field by field, with nested fields named by
+//! their path such as `player_damage.amount`, like the columns of the CSV export.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::loader::AssetCategory;
+use crate::parser::assets::{AsBaseAsset, Asset};
+use crate::parser::guid::Guid;
+use crate::query;
+
+/// Identifies an asset in a diff.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct AssetSummary {
+    pub kind: &'static str,
+    pub guid: Guid,
+    pub id: u16,
+    pub name: String,
+}
+
+impl AssetSummary {
+    pub fn of(asset: &Asset) -> AssetSummary {
+        let base = asset.base_asset();
+
+        AssetSummary {
+            kind: asset.kind(),
+            guid: base.guid,
+            id: base.id,
+            name: base.name.clone(),
+        }
+    }
+}
+
+impl fmt::Display for AssetSummary {
+    /// Formats the asset as e.g. `gun 363 (Maplestrike)`, or by GUID when it has no ID.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.id != 0 {
+            write!(f, "{} {} ({})", self.kind, self.id, self.name)
+        } else {
+            write!(f, "{} {} ({})", self.kind, self.guid, self.name)
+        }
+    }
+}
+
+/// A field whose value differs. A field that only one side has is `null` on the other.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// An asset found in both sets, which was renamed or had fields change.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct AssetChange {
+    pub old: AssetSummary,
+    pub new: AssetSummary,
+
+    /// Every changed field, besides the name, sorted by field.
+    pub fields: Vec<FieldChange>,
+}
+
+impl AssetChange {
+    pub fn is_renamed(&self) -> bool {
+        self.old.name != self.new.name
+    }
+}
+
+/// Everything that differs between two sets of assets, see the [module documentation](self).
+#[derive(Debug, Default, Clone, serde::Serialize)]
+pub struct AssetDiff {
+    /// Assets only in the new set, in its order.
+    pub added: Vec<AssetSummary>,
+
+    /// Assets only in the old set, in its order.
+    pub removed: Vec<AssetSummary>,
+
+    /// Assets in both sets that differ, in the order of the old set.
+    pub changed: Vec<AssetChange>,
+}
+
+impl AssetDiff {
+    pub fn new(old: &[Asset], new: &[Asset]) -> AssetDiff {
+        let mut guids = HashMap::new();
+        let mut ids = HashMap::new();
+        for (index, asset) in new.iter().enumerate() {
+            let base = asset.base_asset();
+            if !base.guid.is_nil() {
+                guids.entry(base.guid).or_insert(index);
+            }
+            if base.id != 0 {
+                let key = (AssetCategory::of(base.r#type), base.id);
+                ids.entry(key).or_insert(index);
+            }
+        }
+
+        let mut diff = AssetDiff::default();
+        let mut matched = vec![false; new.len()];
+
+        for old_asset in old {
+            let base = old_asset.base_asset();
+            let by_guid = (!base.guid.is_nil())
+                .then(|| guids.get(&base.guid))
+                .flatten();
+            // Only fall back to the ID when one side has no GUID, as two different GUIDs are
+            // two different assets.
+            let by_id = || {
+                ids.get(&(AssetCategory::of(base.r#type), base.id))
+                    .filter(|&&index| base.guid.is_nil() || new[index].base_asset().guid.is_nil())
+            };
+
+            let index = by_guid
+                .or_else(|| (base.id != 0).then(by_id).flatten())
+                .filter(|&&index| !matched[index]);
+            let Some(&index) = index else {
+                diff.removed.push(AssetSummary::of(old_asset));
+                continue;
+            };
+            matched[index] = true;
+
+            let new_asset = &new[index];
+            let fields = field_changes(old_asset, new_asset);
+            let change = AssetChange {
+                old: AssetSummary::of(old_asset),
+                new: AssetSummary::of(new_asset),
+                fields,
+            };
+
+            if change.is_renamed() || !change.fields.is_empty() {
+                diff.changed.push(change);
+            }
+        }
+
+        diff.added = new
+            .iter()
+            .zip(&matched)
+            .filter(|(_, matched)| !**matched)
+            .map(|(asset, _)| AssetSummary::of(asset))
+            .collect();
+
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Formats the diff as a Markdown patch notes section.
+    pub fn markdown(&self) -> String {
+        let mut out = String::from("## Changes\n");
+
+        if self.is_empty() {
+            out.push_str("\nNo changes.\n");
+            return out;
+        }
+
+        if !self.added.is_empty() {
+            out.push_str("\n### Added\n\n");
+            for asset in &self.added {
+                out.push_str(&format!("- {}\n", asset));
+            }
+        }
+
+        if !self.removed.is_empty() {
+            out.push_str("\n### Removed\n\n");
+            for asset in &self.removed {
+                out.push_str(&format!("- {}\n", asset));
+            }
+        }
+
+        let renamed: Vec<&AssetChange> = self.renamed().collect();
+        if !renamed.is_empty() {
+            out.push_str("\n### Renamed\n\n");
+            for change in renamed {
+                out.push_str(&format!("- {} → {}\n", change.old, change.new.name));
+            }
+        }
+
+        let changed: Vec<&AssetChange> = self
+            .changed
+            .iter()
+            .filter(|change| !change.fields.is_empty())
+            .collect();
+        if !changed.is_empty() {
+            out.push_str("\n### Changed\n");
+            for change in changed {
+                out.push_str(&format!("\n#### {}\n\n", change.new.name));
+                for field in &change.fields {
+                    out.push_str(&format!(
+                        "- `{}`: {} → {}\n",
+                        field.field,
+                        query::value_text(&field.old),
+                        query::value_text(&field.new)
+                    ));
+                }
+            }
+        }
+
+        out
+    }
+
+    fn renamed(&self) -> impl Iterator<Item = &AssetChange> {
+        self.changed.iter().filter(|change| change.is_renamed())
+    }
+}
+
+impl fmt::Display for AssetDiff {
+    /// Formats the diff with one line per change, such as
+    /// `Maplestrike.player_damage.amount 40 → 42`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for asset in &self.added {
+            writeln!(f, "added {}", asset)?;
+        }
+        for asset in &self.removed {
+            writeln!(f, "removed {}", asset)?;
+        }
+        for change in self.renamed() {
+            writeln!(f, "renamed {} → {}", change.old, change.new.name)?;
+        }
+        for change in &self.changed {
+            for field in &change.fields {
+                writeln!(
+                    f,
+                    "{}.{} {} → {}",
+                    change.new.name,
+                    field.field,
+                    query::value_text(&field.old),
+                    query::value_text(&field.new)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Every field that differs between two assets, besides the name.
+fn field_changes(old: &Asset, new: &Asset) -> Vec<FieldChange> {
+    let mut old_fields = query::fields(old);
+    let mut new_fields = query::fields(new);
+
+    let mut fields: Vec<String> = old_fields
+        .keys()
+        .chain(new_fields.keys())
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter(|field| field != "name")
+        .filter_map(|field| {
+            let old = old_fields.remove(&field).unwrap_or(Value::Null);
+            let new = new_fields.remove(&field).unwrap_or(Value::Null);
+
+            (old != new).then_some(FieldChange { field, old, new })
+        })
+        .collect()
+}
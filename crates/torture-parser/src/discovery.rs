@@ -0,0 +1,87 @@
+//! Finding the data file of every asset below a folder, without needing a masterbundle.
+//!
+//! An asset is a folder holding either `Asset.dat` or a `.dat` named after the folder, such as
+//! `Items/Guns/Maplestrike/Maplestrike.dat`. Standalone `.asset` files, which may hold several
+//! assets, are data files of their own, see [`document`](crate::parser::document).
+//! Masterbundles list the folders of the assets they hold, [`merge_listed`] adds those to what
+//! was found on disk.
+
+use std::path::{Path, PathBuf};
+
+use crate::get_file_stem;
+use crate::source::AssetSource;
+
+/// Name of the data file used by newer content, instead of one named after the folder.
+pub const ASSET_DAT: &str = "Asset.dat";
+
+/// Extension of standalone files in the v2 format.
+pub const ASSET_EXTENSION: &str = "asset";
+
+/// Returns the data file of the asset in `directory`, if it is an asset.
+///
+/// `Asset.dat` is used over `<Stem>.dat` when both exist, like the game does.
+pub fn data_file(source: &dyn AssetSource, directory: &Path) -> Option<PathBuf> {
+    let asset_dat = directory.join(ASSET_DAT);
+    if source.is_file(&asset_dat) {
+        return Some(asset_dat);
+    }
+
+    let stem = get_file_stem(directory)?;
+    let data_file = directory.join(format!("{}.dat", stem));
+
+    source.is_file(&data_file).then_some(data_file)
+}
+
+/// Walks `root` and returns every data file below it, including `.asset` files, sorted by path.
+///
+/// Folders inside an asset are searched too, as they can hold assets of their own, such as
+/// `Items/Ration/Effects/Crumbs/Crumbs.dat`.
+pub fn find_assets(source: &dyn AssetSource, root: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut data_files = Vec::new();
+    let mut directories = vec![root.to_path_buf()];
+
+    while let Some(directory) = directories.pop() {
+        if directory != root {
+            data_files.extend(data_file(source, &directory));
+        }
+
+        for path in source.read_dir(&directory)? {
+            if source.is_dir(&path) {
+                directories.push(path);
+            } else if path.extension().is_some_and(|ext| ext == ASSET_EXTENSION) {
+                data_files.push(path);
+            }
+        }
+    }
+
+    data_files.sort();
+    Ok(data_files)
+}
+
+/// Adds the data files of asset folders listed by a masterbundle, relative to `root`, that
+/// `data_files` does not hold yet.
+///
+/// Listed folders without a data file on disk are skipped.
+pub fn merge_listed<I>(
+    source: &dyn AssetSource,
+    data_files: &mut Vec<PathBuf>,
+    root: &Path,
+    listed: I,
+) where
+    I: IntoIterator,
+    I::Item: AsRef<Path>,
+{
+    for directory in listed {
+        let directory = directory.as_ref();
+        if directory.as_os_str().is_empty() || directory == Path::new(".") {
+            continue;
+        }
+
+        if let Some(data_file) = data_file(source, &root.join(directory)) {
+            data_files.push(data_file);
+        }
+    }
+
+    data_files.sort();
+    data_files.dedup();
+}
@@ -1,8 +1,22 @@
 #![feature(str_split_whitespace_remainder)]
 
+// Lets `#[derive(DatAsset)]` refer to this crate by name from within it.
+extern crate self as torture_parser;
+
 use std::path::Path;
 
+pub mod ballistics;
+pub mod damage;
+pub mod diff;
+pub mod discovery;
+pub mod lint;
+pub mod loader;
+pub mod loadout;
+pub mod lookup;
 pub mod parser;
+pub mod query;
+pub mod raid;
+pub mod source;
 
 pub fn get_file_stem(path: &Path) -> Option<String> {
     if let Some(file_stem) = path.file_stem() {
@@ -0,0 +1,786 @@
+//! Checking content for mistakes the game ignores, or only reports once the asset is used.
+//!
+//! [`lint`] reads every asset below a folder, checks each data file on its own, then checks
+//! the assets against each other and against vanilla. [`Rule::MissingReference`] and
+//! [`Rule::UnusedCaliber`] need every asset the content can refer to, so they only run when
+//! vanilla assets are given.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::discovery;
+use crate::loader::AssetCategory;
+use crate::parser::assets::{AsBaseAsset, AsCaliber, Asset, BaseAsset, Type};
+use crate::parser::dat::{self, DatAsset, DatField, DatFieldKind};
+use crate::parser::document::{self, Node};
+use crate::parser::guid::Guid;
+use crate::source::AssetSource;
+
+/// Keys whose value references another asset, by the type of the asset holding them. A
+/// category of `None` matches an asset of any category.
+const REFERENCES: &[(Type, &str, Option<AssetCategory>)] = &[
+    (Type::Gun, "Sight", Some(AssetCategory::Item)),
+    (Type::Gun, "Tactical", Some(AssetCategory::Item)),
+    (Type::Gun, "Grip", Some(AssetCategory::Item)),
+    (Type::Gun, "Barrel", Some(AssetCategory::Item)),
+    (Type::Gun, "Magazine", Some(AssetCategory::Item)),
+    (
+        Type::Gun,
+        "Magazine_Replacement_#_ID",
+        Some(AssetCategory::Item),
+    ),
+    (Type::Throwable, "Explosion", Some(AssetCategory::Effect)),
+    (Type::Charge, "Explosion2", Some(AssetCategory::Effect)),
+    (Type::Spawn, "Table_#_Spawn_ID", Some(AssetCategory::Spawn)),
+    (Type::Spawn, "Table_#_Asset_ID", None),
+    (Type::Spawn, "LegacySpawnId", Some(AssetCategory::Spawn)),
+    (Type::Spawn, "LegacyAssetId", None),
+    (Type::Spawn, "Guid", None),
+];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum Rule {
+    /// A file or asset that could not be parsed at all.
+    ParseError,
+
+    /// A key that is not read, with the closest key that is when it is probably a typo.
+    UnknownKey,
+
+    /// A key set more than once in the same asset.
+    DuplicateKey,
+
+    /// A number outside of the range of the type it is read as, such as `Wear 300`.
+    OutOfRange,
+
+    /// A value that does not parse as the type it is read as.
+    InvalidValue,
+
+    /// A value the game does not know, such as a misspelled enum variant.
+    UnknownValue,
+
+    /// An asset folder without an `English.dat`.
+    MissingLocalization,
+
+    /// An asset without a GUID, which other assets can only refer to by its legacy ID.
+    MissingGuid,
+
+    /// Two assets with the same GUID.
+    GuidCollision,
+
+    /// Two assets with the same legacy ID in the same category, the later one overrides the
+    /// other.
+    IdCollision,
+
+    /// A reference to an asset that neither the content nor vanilla has.
+    MissingReference,
+
+    /// A caliber of a magazine or attachment that no gun accepts.
+    UnusedCaliber,
+}
+
+impl Rule {
+    /// Name of the rule, such as `unknown-key`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::ParseError => "parse-error",
+            Rule::UnknownKey => "unknown-key",
+            Rule::DuplicateKey => "duplicate-key",
+            Rule::OutOfRange => "out-of-range",
+            Rule::InvalidValue => "invalid-value",
+            Rule::UnknownValue => "unknown-value",
+            Rule::MissingLocalization => "missing-localization",
+            Rule::MissingGuid => "missing-guid",
+            Rule::GuidCollision => "guid-collision",
+            Rule::IdCollision => "id-collision",
+            Rule::MissingReference => "missing-reference",
+            Rule::UnusedCaliber => "unused-caliber",
+        }
+    }
+
+    /// Errors are mistakes that break the asset, warnings may be intended.
+    pub fn severity(self) -> Severity {
+        match self {
+            Rule::ParseError
+            | Rule::OutOfRange
+            | Rule::InvalidValue
+            | Rule::GuidCollision
+            | Rule::MissingReference => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+/// A mistake found by [`lint`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Lint {
+    pub rule: Rule,
+    pub file: PathBuf,
+
+    /// Line of `file` the mistake is on, starting at 1, if it is on a single line.
+    pub line: Option<usize>,
+
+    pub message: String,
+    pub help: Option<String>,
+}
+
+impl Lint {
+    pub fn severity(&self) -> Severity {
+        self.rule.severity()
+    }
+
+    fn new(rule: Rule, file: &Path, line: Option<usize>, message: String) -> Lint {
+        Lint {
+            rule,
+            file: file.to_path_buf(),
+            line,
+            message,
+            help: None,
+        }
+    }
+
+    fn help(mut self, help: String) -> Lint {
+        self.help = Some(help);
+        self
+    }
+}
+
+impl fmt::Display for Lint {
+    /// Formats the lint like a compiler diagnostic, e.g.
+    ///
+    /// ```text
+    /// warning[unknown-key]: `Player_Damag` is not read
+    ///   --> Items/Rifle/Rifle.dat:12
+    ///    = help: did you mean `Player_Damage`?
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}[{}]: {}",
+            self.severity(),
+            self.rule.name(),
+            self.message
+        )?;
+        match self.line {
+            Some(line) => write!(f, "  --> {}:{}", self.file.display(), line)?,
+            None => write!(f, "  --> {}", self.file.display())?,
+        }
+        if let Some(help) = &self.help {
+            write!(f, "\n   = help: {}", help)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A key of an asset, with the line of the file it is on.
+#[derive(Debug, Clone)]
+struct Entry {
+    key: String,
+    value: String,
+    line: Option<usize>,
+}
+
+/// An asset of the linted content.
+struct Linted {
+    asset: Asset,
+    file: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Linted {
+    fn line_of(&self, key: &str) -> Option<usize> {
+        line_of(&self.entries, key)
+    }
+}
+
+/// Lints every asset below `root`, see the [module documentation](self).
+///
+/// `vanilla` is every asset of the game, which the content may override or refer to. Lints
+/// are sorted by file and line.
+pub fn lint(source: &dyn AssetSource, root: &Path, vanilla: &[Asset]) -> anyhow::Result<Vec<Lint>> {
+    let mut lints = Vec::new();
+    let mut assets = Vec::new();
+
+    for data_file in discovery::find_assets(source, root)? {
+        lint_file(source, &data_file, &mut assets, &mut lints);
+    }
+
+    check_collisions(&assets, vanilla, &mut lints);
+    if !vanilla.is_empty() {
+        check_references(&assets, vanilla, &mut lints);
+        check_calibers(&assets, vanilla, &mut lints);
+    }
+
+    lints.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    Ok(lints)
+}
+
+fn lint_file(
+    source: &dyn AssetSource,
+    data_file: &Path,
+    assets: &mut Vec<Linted>,
+    lints: &mut Vec<Lint>,
+) {
+    let parse_error =
+        |error: anyhow::Error| Lint::new(Rule::ParseError, data_file, None, format!("{:#}", error));
+
+    let content = match source.read_to_string(data_file) {
+        Ok(content) => content,
+        Err(error) => return lints.push(parse_error(error)),
+    };
+    let blocks = match document::asset_blocks(&content) {
+        Ok(blocks) => blocks,
+        Err(error) => return lints.push(parse_error(error)),
+    };
+    let mut tables = spawn_tables(&content).into_iter();
+    let directory = data_file.parent().unwrap_or(Path::new(""));
+    let mut lines = LineFinder::new(&content);
+
+    for block in &blocks {
+        let mut entries = entries(block, &mut lines);
+        let start = lints.len();
+
+        let r#type = entries
+            .iter()
+            .find(|entry| entry.key == "Type")
+            .and_then(|entry| dat::read_value::<Type>("Type", &entry.value).ok())
+            .unwrap_or_default();
+        check_keys(data_file, r#type, &entries, lints);
+
+        let (asset, diagnostics) = match Asset::parse_from(source, directory, block) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                // Invalid values already say why most assets fail to parse.
+                if !lints[start..]
+                    .iter()
+                    .any(|lint| lint.severity() == Severity::Error)
+                {
+                    lints.push(parse_error(error));
+                }
+                continue;
+            }
+        };
+
+        // Assets of `.asset` files are localized elsewhere, if at all.
+        if !asset.base_asset().localized && data_file.extension().is_some_and(|ext| ext == "dat") {
+            lints.push(
+                Lint::new(
+                    Rule::MissingLocalization,
+                    data_file,
+                    None,
+                    "`English.dat` is missing, so the asset has no name".to_string(),
+                )
+                .help(format!("add `{}`", directory.join("English.dat").display())),
+            );
+        }
+
+        for diagnostic in diagnostics {
+            // Numbers that do not parse are already reported as invalid or out of range.
+            let line = line_of(&entries, &diagnostic.key);
+            let reported = lints[start..].iter().any(|lint| {
+                lint.line == line && matches!(lint.rule, Rule::InvalidValue | Rule::OutOfRange)
+            });
+            if reported {
+                continue;
+            }
+
+            lints.push(Lint::new(
+                Rule::UnknownValue,
+                data_file,
+                line,
+                diagnostic.to_string(),
+            ));
+        }
+
+        if asset.base_asset().guid.is_nil() {
+            lints.push(
+                Lint::new(
+                    Rule::MissingGuid,
+                    data_file,
+                    line_of(&entries, "ID"),
+                    "the asset has no GUID".to_string(),
+                )
+                .help("other assets can only refer to it by its legacy ID".to_string()),
+            );
+        }
+
+        if document::is_wrapped(&content) {
+            entries.extend(tables.next().unwrap_or_default());
+        }
+        assets.push(Linted {
+            asset,
+            file: data_file.to_path_buf(),
+            entries,
+        });
+    }
+}
+
+/// Checks every key of an asset against the keys read by the class of its type.
+fn check_keys(file: &Path, r#type: Type, entries: &[Entry], lints: &mut Vec<Lint>) {
+    let schema = Asset::schema_of(r#type);
+    // Types without a class of their own read keys the parser does not know about.
+    let has_class = schema.len() > BaseAsset::schema().len();
+    let mut seen: HashMap<&str, Option<usize>> = HashMap::new();
+
+    for entry in entries {
+        if let Some(first) = seen.insert(&entry.key, entry.line) {
+            let mut lint = Lint::new(
+                Rule::DuplicateKey,
+                file,
+                entry.line,
+                format!("`{}` is set more than once", entry.key),
+            );
+            if let Some(first) = first {
+                lint = lint.help(format!("it is first set on line {}", first));
+            }
+            lints.push(lint);
+        }
+
+        let normalized = normalize(&entry.key);
+        match schema
+            .iter()
+            .find(|field| field.key == entry.key || field.key == normalized)
+        {
+            Some(field) => check_value(file, field, entry, lints),
+            None if has_class && !is_legacy_rgb(&entry.key, &schema) => {
+                let mut lint = Lint::new(
+                    Rule::UnknownKey,
+                    file,
+                    entry.line,
+                    format!("`{}` is not read", entry.key),
+                );
+                if let Some(suggestion) = suggest(&entry.key, &schema) {
+                    lint = lint.help(format!("did you mean `{}`?", suggestion));
+                }
+                lints.push(lint);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Checks that a number or bool parses as the type of its field.
+fn check_value(file: &Path, field: &DatField, entry: &Entry, lints: &mut Vec<Lint>) {
+    if field.kind != DatFieldKind::Value {
+        return;
+    }
+
+    // Like the parser, numbers and bools only read the first word.
+    let word = entry.value.split_whitespace().next().unwrap_or("");
+    let invalid = || {
+        Lint::new(
+            Rule::InvalidValue,
+            file,
+            entry.line,
+            format!(
+                "`{}` is read as {}, but is `{}`",
+                entry.key, field.value_type, entry.value
+            ),
+        )
+    };
+
+    if let Some((min, max)) = integer_range(field.value_type) {
+        match word.parse::<i128>() {
+            Ok(value) if value < min || value > max => lints.push(
+                Lint::new(
+                    Rule::OutOfRange,
+                    file,
+                    entry.line,
+                    format!(
+                        "`{}` is {}, which does not fit in {}",
+                        entry.key, value, field.value_type
+                    ),
+                )
+                .help(format!("use a value from {} to {}", min, max)),
+            ),
+            Ok(_) => {}
+            Err(_) => lints.push(invalid()),
+        }
+    } else if matches!(field.value_type, "f32" | "f64") {
+        if word.parse::<f64>().is_err() {
+            lints.push(invalid());
+        }
+    } else if field.value_type == "bool"
+        && !word.is_empty()
+        && word.to_lowercase().parse::<bool>().is_err()
+    {
+        lints.push(invalid());
+    }
+}
+
+fn integer_range(value_type: &str) -> Option<(i128, i128)> {
+    let range = match value_type {
+        "u8" => (0, u8::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "u64" => (0, u64::MAX as i128),
+        "usize" => (0, usize::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        _ => return None,
+    };
+
+    Some(range)
+}
+
+/// Whether `key` is a legacy `_R`, `_G` or `_B` part of a color key.
+fn is_legacy_rgb(key: &str, schema: &[DatField]) -> bool {
+    ["_R", "_G", "_B"]
+        .iter()
+        .filter_map(|suffix| key.strip_suffix(suffix))
+        .any(|key| schema.iter().any(|field| field.key == key))
+}
+
+/// Returns the known key closest to an unknown `key`, if it is close enough to be a typo.
+fn suggest(key: &str, schema: &[DatField]) -> Option<String> {
+    let normalized = normalize(key);
+
+    let mut best: Option<(usize, &str)> = None;
+    for known in schema.iter().map(|field| field.key) {
+        let distance = distance(&normalized.to_lowercase(), &known.to_lowercase());
+        if best.is_none_or(|(best, _)| distance < best) {
+            best = Some((distance, known));
+        }
+    }
+
+    let (distance, suggestion) = best?;
+    let threshold = if normalized.len() >= 8 { 2 } else { 1 };
+    (distance <= threshold).then(|| denormalize(suggestion, key))
+}
+
+/// Replaces the indices in a key with `#`, e.g. `Blueprint_0_Supply_1_ID` is
+/// `Blueprint_#_Supply_#_ID`.
+fn normalize(key: &str) -> String {
+    key.split('_')
+        .map(|part| {
+            if !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()) {
+                "#"
+            } else {
+                part
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Puts the indices of `key` back into a normalized key, in order.
+fn denormalize(normalized: &str, key: &str) -> String {
+    let mut indices = key
+        .split('_')
+        .filter(|part| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()));
+
+    normalized
+        .split('_')
+        .map(|part| match part {
+            "#" => indices.next().unwrap_or("0"),
+            part => part,
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Edit distance between two strings.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if a == *b {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Reports GUIDs used twice, and legacy IDs used twice in the same category.
+fn check_collisions(assets: &[Linted], vanilla: &[Asset], lints: &mut Vec<Lint>) {
+    let describe = |asset: &Asset| format!("{} `{}`", asset.kind(), asset.base_asset().name);
+
+    let mut guids: HashMap<Guid, String> = HashMap::new();
+    let mut ids: HashMap<(AssetCategory, u16), String> = HashMap::new();
+    for asset in vanilla {
+        let base = asset.base_asset();
+        let description = format!("vanilla {}", describe(asset));
+        guids.insert(base.guid, description.clone());
+        ids.insert((AssetCategory::of(base.r#type), base.id), description);
+    }
+
+    for linted in assets {
+        let base = linted.asset.base_asset();
+        let description = format!("{} in {}", describe(&linted.asset), linted.file.display());
+
+        if !base.guid.is_nil() {
+            match guids.get(&base.guid) {
+                Some(other) => {
+                    lints.push(Lint::new(
+                        Rule::GuidCollision,
+                        &linted.file,
+                        linted.line_of("GUID"),
+                        format!("GUID {} is also used by {}", base.guid, other),
+                    ));
+                    // Assets with the same GUID are the same asset, so the ID is expected to
+                    // match as well.
+                    continue;
+                }
+                None => {
+                    guids.insert(base.guid, description.clone());
+                }
+            }
+        }
+
+        if base.id != 0 {
+            let category = AssetCategory::of(base.r#type);
+            match ids.get(&(category, base.id)) {
+                Some(other) => lints.push(
+                    Lint::new(
+                        Rule::IdCollision,
+                        &linted.file,
+                        linted.line_of("ID"),
+                        format!("{} ID {} is also used by {}", category, base.id, other),
+                    )
+                    .help("the asset loaded last replaces the other".to_string()),
+                ),
+                None => {
+                    ids.insert((category, base.id), description);
+                }
+            }
+        }
+    }
+}
+
+/// Reports references to assets that neither the content nor vanilla has.
+fn check_references(assets: &[Linted], vanilla: &[Asset], lints: &mut Vec<Lint>) {
+    let every = assets
+        .iter()
+        .map(|linted| linted.asset.base_asset())
+        .chain(vanilla.iter().map(|asset| asset.base_asset()));
+
+    let mut guids = HashSet::new();
+    let mut ids = HashSet::new();
+    for base in every {
+        guids.insert(base.guid);
+        ids.insert((AssetCategory::of(base.r#type), base.id));
+    }
+    let exists = |category: Option<AssetCategory>, id: u16| match category {
+        Some(category) => ids.contains(&(category, id)),
+        None => ids
+            .iter()
+            .any(|(other, other_id)| *other != AssetCategory::Spawn && *other_id == id),
+    };
+
+    for linted in assets {
+        let r#type = linted.asset.base_asset().r#type;
+
+        for entry in &linted.entries {
+            let key = normalize(&entry.key);
+            let Some((_, _, category)) = REFERENCES
+                .iter()
+                .find(|(other, other_key, _)| *other == r#type && *other_key == key)
+            else {
+                continue;
+            };
+
+            let value = entry.value.split_whitespace().next().unwrap_or("");
+            let missing = if let Ok(id) = value.parse::<u16>() {
+                id != 0 && !exists(*category, id)
+            } else if let Ok(guid) = value.parse::<Guid>() {
+                !guid.is_nil() && !guids.contains(&guid)
+            } else {
+                // The parser already reports values that are neither.
+                false
+            };
+
+            if missing {
+                let target = category.map_or("asset".to_string(), |category| category.to_string());
+                lints.push(Lint::new(
+                    Rule::MissingReference,
+                    &linted.file,
+                    entry.line,
+                    format!(
+                        "`{}` refers to {} {}, which does not exist",
+                        entry.key, target, value
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Reports calibers of magazines and attachments that no gun of the content or vanilla
+/// accepts.
+fn check_calibers(assets: &[Linted], vanilla: &[Asset], lints: &mut Vec<Lint>) {
+    let mut magazine_calibers = HashSet::new();
+    let mut attachment_calibers = HashSet::new();
+    for asset in assets.iter().map(|linted| &linted.asset).chain(vanilla) {
+        if let Asset::Gun(gun) = asset {
+            magazine_calibers.extend(gun.magazine_calibers.iter().copied());
+            attachment_calibers.extend(gun.attachment_calibers.iter().copied());
+        }
+    }
+
+    for linted in assets {
+        let (calibers, accepted, list) = match &linted.asset {
+            Asset::Magazine(asset) => (asset.caliber(), &magazine_calibers, "Magazine_Caliber"),
+            Asset::Barrel(asset) => (asset.caliber(), &attachment_calibers, "Attachment_Caliber"),
+            Asset::Grip(asset) => (asset.caliber(), &attachment_calibers, "Attachment_Caliber"),
+            Asset::Sight(asset) => (asset.caliber(), &attachment_calibers, "Attachment_Caliber"),
+            Asset::Tactical(asset) => (asset.caliber(), &attachment_calibers, "Attachment_Caliber"),
+            _ => continue,
+        };
+
+        for caliber in &calibers.calibers {
+            if *caliber == 0 || accepted.contains(caliber) {
+                continue;
+            }
+
+            let line = linted
+                .entries
+                .iter()
+                .find(|entry| {
+                    matches!(normalize(&entry.key).as_str(), "Caliber" | "Caliber_#")
+                        && entry.value.split_whitespace().next() == Some(&caliber.to_string())
+                })
+                .and_then(|entry| entry.line);
+            lints.push(
+                Lint::new(
+                    Rule::UnusedCaliber,
+                    &linted.file,
+                    line,
+                    format!("no gun accepts caliber {}", caliber),
+                )
+                .help(format!("guns accept calibers listed as `{}_#`", list)),
+            );
+        }
+    }
+}
+
+fn line_of(entries: &[Entry], key: &str) -> Option<usize> {
+    entries
+        .iter()
+        .find(|entry| entry.key == key)
+        .and_then(|entry| entry.line)
+}
+
+/// Reads the keys of an asset block, leaving out the contents of nested lists and
+/// dictionaries such as `Distance_Markers`.
+fn entries(block: &str, lines: &mut LineFinder) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    let mut depth = 0usize;
+
+    for line in block.lines() {
+        match line.trim() {
+            "" => continue,
+            "[" | "{" => depth += 1,
+            "]" | "}" => depth = depth.saturating_sub(1),
+            trimmed if trimmed.starts_with("//") => continue,
+            _ if depth > 0 => continue,
+            _ => {
+                let Some((key, value)) = dat::lines(line).next() else {
+                    continue;
+                };
+
+                entries.push(Entry {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    line: lines.find(key),
+                });
+            }
+        }
+    }
+
+    entries
+}
+
+/// Entries of the `Tables` list of every `Asset` block of a file in the v2 format, which are
+/// nested and so left out of [`document::asset_blocks`].
+fn spawn_tables(content: &str) -> Vec<Vec<Entry>> {
+    if !document::is_wrapped(content) {
+        return Vec::new();
+    }
+    let Ok(Node::Dictionary(root)) = document::parse(content) else {
+        return Vec::new();
+    };
+
+    let mut lines = LineFinder::new(content);
+    root.iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("Asset"))
+        .map(|(_, asset)| {
+            let Some(Node::List(tables)) = asset.get("Tables") else {
+                return Vec::new();
+            };
+
+            tables
+                .iter()
+                .filter_map(Node::as_dictionary)
+                .flatten()
+                .filter_map(|(key, value)| Some((key, value.as_value()?)))
+                .map(|(key, value)| Entry {
+                    key: key.clone(),
+                    value: value.to_string(),
+                    line: lines.find(key),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Finds the lines of keys in a file, in the order they are read.
+struct LineFinder<'a> {
+    lines: Vec<&'a str>,
+    next: usize,
+}
+
+impl<'a> LineFinder<'a> {
+    fn new(content: &'a str) -> LineFinder<'a> {
+        LineFinder {
+            lines: content.lines().collect(),
+            next: 0,
+        }
+    }
+
+    /// Returns the first line after the last one found that sets `key`, or the first line
+    /// of the file that does.
+    fn find(&mut self, key: &str) -> Option<usize> {
+        let sets_key = |line: &&str| {
+            line.split_whitespace()
+                .next()
+                .is_some_and(|first| first.trim_matches('"') == key)
+        };
+
+        if let Some(offset) = self.lines[self.next..].iter().position(sets_key) {
+            let index = self.next + offset;
+            self.next = index + 1;
+            return Some(index + 1);
+        }
+
+        self.lines.iter().position(sets_key).map(|index| index + 1)
+    }
+}
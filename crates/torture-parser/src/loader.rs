@@ -0,0 +1,437 @@
+//! Loading assets from several roots, such as vanilla Bundles, workshop mods and a server's
+//! own Bundles, in load order.
+//!
+//! Assets loaded later shadow earlier ones with the same GUID, or with the same legacy ID in
+//! the same [`AssetCategory`], which is how mods override vanilla items. Every shadowed asset
+//! is recorded as a [`Conflict`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use crate::discovery;
+use crate::parser::assets::{BaseAsset, Type};
+use crate::parser::dat::DatAsset;
+use crate::parser::document;
+use crate::parser::guid::Guid;
+use crate::source::{AssetSource, FileSystem};
+
+/// Steam app ID of Unturned, the folder workshop content is downloaded into.
+pub const UNTURNED_APP_ID: &str = "304930";
+
+/// Where a root of assets comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Origin {
+    /// The game's own `Bundles` folder.
+    Vanilla,
+
+    /// A Steam Workshop mod, by its file ID.
+    Workshop(u64),
+
+    /// A server's own `Bundles` folder.
+    Server,
+
+    /// Any other folder, such as unpacked mod content.
+    Custom(String),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Vanilla => f.write_str("vanilla"),
+            Origin::Workshop(id) => write!(f, "mod {}", id),
+            Origin::Server => f.write_str("server"),
+            Origin::Custom(name) => f.write_str(name),
+        }
+    }
+}
+
+/// Namespace of legacy IDs, two assets only conflict by ID within the same category.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum AssetCategory {
+    Item,
+    Effect,
+    Resource,
+    Vehicle,
+    Animal,
+    Spawn,
+    Npc,
+    Other,
+}
+
+impl AssetCategory {
+    pub fn of(r#type: Type) -> AssetCategory {
+        match r#type {
+            Type::Effect => AssetCategory::Effect,
+            Type::Resource => AssetCategory::Resource,
+            Type::Vehicle => AssetCategory::Vehicle,
+            Type::Animal => AssetCategory::Animal,
+            Type::Spawn => AssetCategory::Spawn,
+            Type::Npc => AssetCategory::Npc,
+            Type::Unknown | Type::Decal => AssetCategory::Other,
+            _ => AssetCategory::Item,
+        }
+    }
+}
+
+impl fmt::Display for AssetCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AssetCategory::Item => "item",
+            AssetCategory::Effect => "effect",
+            AssetCategory::Resource => "resource",
+            AssetCategory::Vehicle => "vehicle",
+            AssetCategory::Animal => "animal",
+            AssetCategory::Spawn => "spawn",
+            AssetCategory::Npc => "npc",
+            AssetCategory::Other => "asset",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// A folder to load assets from.
+#[derive(Debug, Clone)]
+pub struct Root {
+    pub origin: Origin,
+    pub path: PathBuf,
+}
+
+/// Collects roots in load order, see the [module documentation](self).
+pub struct Loader {
+    source: Box<dyn AssetSource>,
+    roots: Vec<Root>,
+    listed: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl Loader {
+    /// A loader reading roots from the filesystem.
+    pub fn new() -> Loader {
+        Loader::with_source(FileSystem)
+    }
+
+    /// A loader reading roots from `source`, such as an unpacked mod upload.
+    pub fn with_source(source: impl AssetSource + 'static) -> Loader {
+        Loader {
+            source: Box::new(source),
+            roots: Vec::new(),
+            listed: HashMap::new(),
+        }
+    }
+
+    pub fn root(mut self, origin: Origin, path: impl Into<PathBuf>) -> Loader {
+        self.roots.push(Root {
+            origin,
+            path: path.into(),
+        });
+        self
+    }
+
+    pub fn vanilla(self, path: impl Into<PathBuf>) -> Loader {
+        self.root(Origin::Vanilla, path)
+    }
+
+    pub fn server(self, path: impl Into<PathBuf>) -> Loader {
+        self.root(Origin::Server, path)
+    }
+
+    /// Adds every mod in a workshop folder, ordered by their file ID.
+    ///
+    /// `workshop` is either Steam's `workshop` folder or its `content/304930` subfolder.
+    pub fn workshop(self, workshop: impl AsRef<Path>) -> anyhow::Result<Loader> {
+        let mods = workshop_mods(&*self.source, workshop.as_ref())?;
+        let order: Vec<u64> = mods.iter().map(|(id, _)| *id).collect();
+
+        self.workshop_ordered(workshop, &order)
+    }
+
+    /// Adds the mods of a workshop folder in the given order, such as the `File_IDs` of a
+    /// server's `WorkshopDownloadConfig.json`. Mods that are not downloaded are skipped.
+    pub fn workshop_ordered(
+        mut self,
+        workshop: impl AsRef<Path>,
+        order: &[u64],
+    ) -> anyhow::Result<Loader> {
+        let mods: HashMap<u64, PathBuf> = workshop_mods(&*self.source, workshop.as_ref())?
+            .into_iter()
+            .collect();
+
+        for id in order {
+            if let Some(path) = mods.get(id) {
+                let bundles = mod_bundles(&*self.source, path);
+                self = self.root(Origin::Workshop(*id), bundles);
+            }
+        }
+
+        Ok(self)
+    }
+
+    pub fn roots(&self) -> &[Root] {
+        &self.roots
+    }
+
+    /// Also loads the asset folders listed for the root at `path`, such as by its masterbundle,
+    /// relative to it. See [`discovery::merge_listed`].
+    pub fn listed<I>(mut self, path: &Path, folders: I) -> Loader
+    where
+        I: IntoIterator,
+        I::Item: Into<PathBuf>,
+    {
+        self.listed
+            .entry(path.to_path_buf())
+            .or_default()
+            .extend(folders.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reads the base asset of everything in every root, applying overrides in load order.
+    pub fn load(&self) -> anyhow::Result<Catalog> {
+        let mut catalog = CatalogBuilder::default();
+
+        for root in &self.roots {
+            let mut data_files = discovery::find_assets(&*self.source, &root.path)?;
+            if let Some(listed) = self.listed.get(&root.path) {
+                discovery::merge_listed(&*self.source, &mut data_files, &root.path, listed);
+            }
+
+            for data_file in data_files {
+                let directory = data_file
+                    .parent()
+                    .context("Failed to get the parent of data file")?;
+
+                let bases = self.source.read_to_string(&data_file).and_then(|content| {
+                    document::asset_blocks(&content)?
+                        .iter()
+                        .map(|block| BaseAsset::parse_from(&*self.source, directory, block))
+                        .map(|parsed| parsed.map(|(base, _)| base))
+                        .collect::<anyhow::Result<Vec<_>>>()
+                });
+
+                match bases {
+                    Ok(bases) => {
+                        for (block, base) in bases.into_iter().enumerate() {
+                            catalog.insert(LoadedAsset {
+                                origin: root.origin.clone(),
+                                data_file: data_file.clone(),
+                                block,
+                                base,
+                            });
+                        }
+                    }
+                    Err(error) => catalog.failures.push(LoadFailure {
+                        origin: root.origin.clone(),
+                        data_file,
+                        error: format!("{:#}", error),
+                    }),
+                }
+            }
+        }
+
+        Ok(catalog.finish())
+    }
+}
+
+impl Default for Loader {
+    fn default() -> Loader {
+        Loader::new()
+    }
+}
+
+impl fmt::Debug for Loader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Loader")
+            .field("roots", &self.roots)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Lists the mods in a workshop folder as `(file ID, folder)`, ordered by file ID.
+pub fn workshop_mods(
+    source: &dyn AssetSource,
+    workshop: &Path,
+) -> anyhow::Result<Vec<(u64, PathBuf)>> {
+    let content = workshop.join("content").join(UNTURNED_APP_ID);
+    let content = if source.is_dir(&content) {
+        content
+    } else {
+        workshop.to_path_buf()
+    };
+
+    let entries = source
+        .read_dir(&content)
+        .with_context(|| format!("Failed to read workshop folder: {}", content.display()))?;
+
+    let mut mods = Vec::new();
+    for path in entries {
+        let id = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.parse::<u64>().ok());
+
+        if let (Some(id), true) = (id, source.is_dir(&path)) {
+            mods.push((id, path));
+        }
+    }
+
+    mods.sort_by_key(|(id, _)| *id);
+    Ok(mods)
+}
+
+/// Mods either hold a `Bundles` folder, or put their content at the top level.
+fn mod_bundles(source: &dyn AssetSource, path: &Path) -> PathBuf {
+    let bundles = path.join("Bundles");
+
+    if source.is_dir(&bundles) {
+        bundles
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// An asset that was loaded, along with where it came from.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct LoadedAsset {
+    pub origin: Origin,
+    pub data_file: PathBuf,
+
+    /// Index of the asset among those of its data file, see [`document::asset_blocks`].
+    pub block: usize,
+
+    pub base: BaseAsset,
+}
+
+impl LoadedAsset {
+    pub fn category(&self) -> AssetCategory {
+        AssetCategory::of(self.base.r#type)
+    }
+
+    /// Folder of the asset, which holds its `English.dat`.
+    ///
+    /// Every asset of an `.asset` file shares the folder of that file.
+    pub fn directory(&self) -> &Path {
+        self.data_file.parent().unwrap_or(Path::new(""))
+    }
+}
+
+/// An asset whose data file could not be read or parsed.
+#[derive(Debug, serde::Serialize)]
+pub struct LoadFailure {
+    pub origin: Origin,
+    pub data_file: PathBuf,
+    pub error: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum ConflictKind {
+    Guid(Guid),
+    Id(AssetCategory, u16),
+}
+
+/// An asset that was replaced by one loaded after it.
+#[derive(Debug, serde::Serialize)]
+pub struct Conflict {
+    pub kind: ConflictKind,
+    pub winner: LoadedAsset,
+
+    /// The replaced asset, which is no longer in the [`Catalog`].
+    pub shadowed: LoadedAsset,
+}
+
+impl fmt::Display for Conflict {
+    /// Formats the conflict as e.g. `mod 1234 item 1165 (Maplestrike) shadows vanilla item 1165 (Maplestrike)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |asset: &LoadedAsset| match self.kind {
+            ConflictKind::Guid(guid) => format!(
+                "{} {} {} ({})",
+                asset.origin,
+                asset.category(),
+                guid,
+                asset.base.name
+            ),
+            ConflictKind::Id(category, id) => {
+                format!("{} {} {} ({})", asset.origin, category, id, asset.base.name)
+            }
+        };
+
+        write!(
+            f,
+            "{} shadows {}",
+            describe(&self.winner),
+            describe(&self.shadowed)
+        )
+    }
+}
+
+/// Every asset that is left after overrides, in load order.
+#[derive(Debug, Default, serde::Serialize)]
+pub struct Catalog {
+    pub assets: Vec<LoadedAsset>,
+    pub conflicts: Vec<Conflict>,
+    pub failures: Vec<LoadFailure>,
+}
+
+impl Catalog {
+    pub fn by_guid(&self, guid: Guid) -> Option<&LoadedAsset> {
+        self.assets.iter().find(|asset| asset.base.guid == guid)
+    }
+
+    pub fn by_id(&self, category: AssetCategory, id: u16) -> Option<&LoadedAsset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.base.id == id && asset.category() == category)
+    }
+}
+
+/// Builds a [`Catalog`], keeping shadowed assets as empty slots until the end so indices stay valid.
+#[derive(Default)]
+struct CatalogBuilder {
+    slots: Vec<Option<LoadedAsset>>,
+    guids: HashMap<Guid, usize>,
+    ids: HashMap<(AssetCategory, u16), usize>,
+    conflicts: Vec<Conflict>,
+    failures: Vec<LoadFailure>,
+}
+
+impl CatalogBuilder {
+    /// Adds an asset, replacing any earlier asset it shadows.
+    fn insert(&mut self, asset: LoadedAsset) {
+        let index = self.slots.len();
+        let guid = asset.base.guid;
+        let key = (asset.category(), asset.base.id);
+
+        let by_guid = (!guid.is_nil())
+            .then(|| self.guids.insert(guid, index))
+            .flatten()
+            .map(|shadowed| (shadowed, ConflictKind::Guid(guid)));
+        let by_id = (key.1 != 0)
+            .then(|| self.ids.insert(key, index))
+            .flatten()
+            .map(|shadowed| (shadowed, ConflictKind::Id(key.0, key.1)));
+
+        for (shadowed, kind) in by_guid.into_iter().chain(by_id) {
+            // Already replaced through its GUID, or by an earlier override.
+            let Some(shadowed) = self.slots[shadowed].take() else {
+                continue;
+            };
+
+            self.conflicts.push(Conflict {
+                kind,
+                winner: asset.clone(),
+                shadowed,
+            });
+        }
+
+        self.slots.push(Some(asset));
+    }
+
+    fn finish(self) -> Catalog {
+        Catalog {
+            assets: self.slots.into_iter().flatten().collect(),
+            conflicts: self.conflicts,
+            failures: self.failures,
+        }
+    }
+}
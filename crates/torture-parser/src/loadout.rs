@@ -0,0 +1,281 @@
+//! A gun with its attachments, and the stats that result from them.
+
+use anyhow::bail;
+
+use crate::parser::assets::barrel::ItemBarrelAsset;
+use crate::parser::assets::caliber::ItemCaliberAsset;
+use crate::parser::assets::grip::ItemGripAsset;
+use crate::parser::assets::gun::{Hook, ItemGunAsset, Recoil, Shake, Spread};
+use crate::parser::assets::magazine::ItemMagazineAsset;
+use crate::parser::assets::sight::ItemSightAsset;
+use crate::parser::assets::tactical::ItemTacticalAsset;
+use crate::parser::assets::AsBaseAsset;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Loadout<'a> {
+    pub gun: &'a ItemGunAsset,
+    pub sight: Option<&'a ItemSightAsset>,
+    pub tactical: Option<&'a ItemTacticalAsset>,
+    pub grip: Option<&'a ItemGripAsset>,
+    pub barrel: Option<&'a ItemBarrelAsset>,
+    pub magazine: Option<&'a ItemMagazineAsset>,
+}
+
+/// Stats of a gun once every attachment of a [`Loadout`] is applied.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct LoadoutStats {
+    /// Ticks between two shots.
+    pub firerate: u8,
+
+    /// Multiplier on damage dealt by bullets.
+    pub damage_multiplier: f32,
+
+    pub spread: Spread,
+    pub recoil: Recoil,
+    pub shake: Shake,
+
+    /// Multiplier on scope sway.
+    pub sway_multiplier: f32,
+
+    /// Seconds it takes to aim down sights.
+    pub aim_in_duration: f32,
+
+    /// Multiplier on movement speed while aiming down sights.
+    pub aiming_movement_speed_multiplier: f32,
+
+    /// Gravity applied to bullets each ballistics step.
+    pub ballistic_drop: f32,
+
+    /// Seconds it takes to reload.
+    pub reload_time: f32,
+
+    /// Multiplicative amount of zoom of the sight, or 1 without one.
+    pub zoom: f32,
+
+    /// Number of bullet rays shot.
+    pub pellets: u8,
+
+    /// Every change an attachment made, in the order they were applied.
+    pub changes: Vec<StatChange>,
+}
+
+/// A stat changed by an attachment, e.g. a grip lowering vertical recoil.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct StatChange {
+    /// Name of the attachment.
+    pub attachment: String,
+
+    /// Name of the stat, matching the [`LoadoutStats`] field.
+    pub stat: String,
+
+    pub before: f32,
+    pub after: f32,
+}
+
+impl<'a> Loadout<'a> {
+    /// A gun without any attachments.
+    pub fn new(gun: &'a ItemGunAsset) -> Loadout<'a> {
+        Loadout {
+            gun,
+            sight: None,
+            tactical: None,
+            grip: None,
+            barrel: None,
+            magazine: None,
+        }
+    }
+
+    /// Attachments present in the loadout, with the hook they need on the gun.
+    ///
+    /// Magazines do not need a hook, so theirs is `None`.
+    fn attachments(&self) -> Vec<(Option<Hook>, &'a ItemCaliberAsset)> {
+        let mut attachments = Vec::new();
+
+        if let Some(sight) = self.sight {
+            attachments.push((Some(Hook::Sight), &sight.item_caliber_asset));
+        }
+        if let Some(tactical) = self.tactical {
+            attachments.push((Some(Hook::Tactical), &tactical.item_caliber_asset));
+        }
+        if let Some(grip) = self.grip {
+            attachments.push((Some(Hook::Grip), &grip.item_caliber_asset));
+        }
+        if let Some(barrel) = self.barrel {
+            attachments.push((Some(Hook::Barrel), &barrel.item_caliber_asset));
+        }
+        if let Some(magazine) = self.magazine {
+            attachments.push((None, &magazine.item_caliber_asset));
+        }
+
+        attachments
+    }
+
+    /// Checks that the gun has a hook for every attachment, and shares a caliber with each.
+    ///
+    /// Attachments without any calibers fit every gun.
+    pub fn check(&self) -> anyhow::Result<()> {
+        let gun = self.gun;
+
+        for (hook, attachment) in self.attachments() {
+            let name = attachment.name();
+
+            let calibers = match hook {
+                Some(hook) => {
+                    if !gun.hooks.contains(&hook) {
+                        bail!("{} has no {:?} hook for {}", gun.name(), hook, name);
+                    }
+
+                    &gun.attachment_calibers
+                }
+                None => &gun.magazine_calibers,
+            };
+
+            let fits = attachment.calibers.is_empty()
+                || attachment
+                    .calibers
+                    .iter()
+                    .any(|caliber| calibers.contains(caliber));
+            if !fits {
+                bail!(
+                    "{} shares no caliber with {} (needs one of {:?}, has {:?})",
+                    gun.name(),
+                    name,
+                    calibers,
+                    attachment.calibers
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Applies every attachment to the gun's stats.
+    ///
+    /// This does not check compatibility, see [`Loadout::check`].
+    pub fn stats(&self) -> LoadoutStats {
+        let gun = self.gun;
+        let mut stats = LoadoutStats {
+            firerate: gun.firerate,
+            damage_multiplier: 1.0,
+            spread: gun.spread.clone(),
+            recoil: gun.recoil.clone(),
+            shake: gun.shake.clone(),
+            sway_multiplier: 1.0,
+            aim_in_duration: gun.aim_in_duration,
+            aiming_movement_speed_multiplier: 1.0,
+            ballistic_drop: gun.ballistics.drop,
+            reload_time: gun.reload_time,
+            zoom: self.sight.map_or(1.0, |sight| sight.zoom.max(1.0)),
+            pellets: self.magazine.map_or(1, |magazine| magazine.pellets.max(1)),
+            changes: Vec::new(),
+        };
+
+        for (_, attachment) in self.attachments() {
+            let mut changes = Changes {
+                attachment: attachment.name(),
+                changes: &mut stats.changes,
+            };
+
+            let damage = attachment.ballistic_damage_multiplier;
+            changes.multiply("damage_multiplier", &mut stats.damage_multiplier, damage);
+
+            let spread = attachment.spread;
+            changes.multiply("spread.aim", &mut stats.spread.aim, spread);
+            changes.multiply("spread.hip", &mut stats.spread.hip, spread);
+
+            let recoil_x = attachment.recoil_x;
+            changes.multiply("recoil.min_x", &mut stats.recoil.min_x, recoil_x);
+            changes.multiply("recoil.max_x", &mut stats.recoil.max_x, recoil_x);
+
+            let recoil_y = attachment.recoil_y;
+            changes.multiply("recoil.min_y", &mut stats.recoil.min_y, recoil_y);
+            changes.multiply("recoil.max_y", &mut stats.recoil.max_y, recoil_y);
+
+            let aiming_recoil = attachment.aiming_recoil_multiplier;
+            changes.multiply("recoil.aim", &mut stats.recoil.aim, aiming_recoil);
+
+            let shake = attachment.shake;
+            for (stat, axis) in [
+                ("shake.min_x", &mut stats.shake.min_x),
+                ("shake.min_y", &mut stats.shake.min_y),
+                ("shake.min_z", &mut stats.shake.min_z),
+                ("shake.max_x", &mut stats.shake.max_x),
+                ("shake.max_y", &mut stats.shake.max_y),
+                ("shake.max_z", &mut stats.shake.max_z),
+            ] {
+                changes.multiply(stat, axis, shake);
+            }
+
+            let sway = attachment.sway;
+            changes.multiply("sway_multiplier", &mut stats.sway_multiplier, sway);
+
+            let aim_duration = attachment.aim_duration_multiplier;
+            changes.multiply("aim_in_duration", &mut stats.aim_in_duration, aim_duration);
+
+            let movement = attachment.aiming_movement_speed_multiplier;
+            changes.multiply(
+                "aiming_movement_speed_multiplier",
+                &mut stats.aiming_movement_speed_multiplier,
+                movement,
+            );
+
+            if attachment.firerate != 0 {
+                let before = stats.firerate;
+                let after = (before as i32 - attachment.firerate as i32).clamp(0, u8::MAX as i32);
+                stats.firerate = after as u8;
+                changes.record("firerate", before as f32, after as f32);
+            }
+        }
+
+        if let Some(barrel) = self.barrel {
+            let mut changes = Changes {
+                attachment: barrel.name(),
+                changes: &mut stats.changes,
+            };
+            changes.multiply(
+                "ballistic_drop",
+                &mut stats.ballistic_drop,
+                barrel.ballistic_drop,
+            );
+        }
+
+        if let Some(magazine) = self.magazine {
+            if magazine.speed > 0.0 {
+                let mut changes = Changes {
+                    attachment: magazine.name(),
+                    changes: &mut stats.changes,
+                };
+                changes.multiply("reload_time", &mut stats.reload_time, 1.0 / magazine.speed);
+            }
+        }
+
+        stats
+    }
+}
+
+/// Records the changes made by a single attachment.
+struct Changes<'a> {
+    attachment: &'a str,
+    changes: &'a mut Vec<StatChange>,
+}
+
+impl Changes<'_> {
+    fn multiply(&mut self, stat: &str, value: &mut f32, multiplier: f32) {
+        if multiplier == 1.0 {
+            return;
+        }
+
+        let before = *value;
+        *value *= multiplier;
+        self.record(stat, before, *value);
+    }
+
+    fn record(&mut self, stat: &str, before: f32, after: f32) {
+        self.changes.push(StatChange {
+            attachment: self.attachment.to_string(),
+            stat: stat.to_string(),
+            before,
+            after,
+        });
+    }
+}
@@ -0,0 +1,157 @@
+//! Finding assets by ID, GUID or name, and resolving what they refer to and what refers to
+//! them, such as the attachments that fit a gun or the spawn assets that can roll an item.
+
+use std::collections::HashSet;
+
+use crate::loader::AssetCategory;
+use crate::loadout::Loadout;
+use crate::parser::assets::gun::ItemGunAsset;
+use crate::parser::assets::{AsBaseAsset, Asset};
+use crate::parser::guid::Guid;
+use crate::parser::reference::AssetReference;
+use crate::parser::spawn::{SpawnAsset, SpawnTarget};
+
+/// Parsed assets to look up, such as everything in a bundles directory.
+#[derive(Debug, Copy, Clone)]
+pub struct Lookup<'a> {
+    assets: &'a [&'a Asset],
+}
+
+impl<'a> Lookup<'a> {
+    pub fn new(assets: &'a [&'a Asset]) -> Lookup<'a> {
+        Lookup { assets }
+    }
+
+    /// Indices of the assets `query` names, which is a legacy ID, a GUID, or a name.
+    ///
+    /// Names are matched case-insensitively, and only match part of a name when no name
+    /// matches whole. IDs are only unique within an [`AssetCategory`], so may find several.
+    pub fn find(&self, query: &str) -> Vec<usize> {
+        let query = query.trim();
+        let find = |matches: &dyn Fn(&Asset) -> bool| -> Vec<usize> {
+            (0..self.assets.len())
+                .filter(|&index| matches(self.assets[index]))
+                .collect()
+        };
+
+        if let Ok(id) = query.parse::<u16>() {
+            return find(&|asset| asset.id() == id);
+        }
+        if let Ok(guid) = query.parse::<Guid>() {
+            return find(&|asset| asset.guid() == guid);
+        }
+
+        let query = query.to_lowercase();
+        let exact = find(&|asset| asset.name().to_lowercase() == query);
+        if !exact.is_empty() {
+            return exact;
+        }
+
+        find(&|asset| asset.name().to_lowercase().contains(&query))
+    }
+
+    pub fn by_id(&self, category: AssetCategory, id: u16) -> Option<&'a Asset> {
+        self.assets
+            .iter()
+            .find(|asset| {
+                asset.id() == id && AssetCategory::of(asset.base_asset().r#type) == category
+            })
+            .copied()
+    }
+
+    pub fn by_guid(&self, guid: Guid) -> Option<&'a Asset> {
+        self.assets
+            .iter()
+            .find(|asset| asset.guid() == guid)
+            .copied()
+    }
+
+    /// The asset `reference` points to, looking up legacy IDs in `category`.
+    pub fn resolve<T>(
+        &self,
+        reference: AssetReference<T>,
+        category: AssetCategory,
+    ) -> Option<&'a Asset> {
+        if let Some(id) = reference.id() {
+            self.by_id(category, id)
+        } else {
+            self.by_guid(reference.guid()?)
+        }
+    }
+
+    /// Every sight, tactical, grip, barrel and magazine that fits `gun`, see [`Loadout::check`].
+    pub fn attachments(&self, gun: &ItemGunAsset) -> Vec<&'a Asset> {
+        self.assets
+            .iter()
+            .copied()
+            .filter(|asset| {
+                let mut loadout = Loadout::new(gun);
+                match asset {
+                    Asset::Sight(sight) => loadout.sight = Some(sight),
+                    Asset::Tactical(tactical) => loadout.tactical = Some(tactical),
+                    Asset::Grip(grip) => loadout.grip = Some(grip),
+                    Asset::Barrel(barrel) => loadout.barrel = Some(barrel),
+                    Asset::Magazine(magazine) => loadout.magazine = Some(magazine),
+                    _ => return false,
+                }
+
+                loadout.check().is_ok()
+            })
+            .collect()
+    }
+
+    /// Spawn assets that can roll `asset`, directly or through other spawn assets, in the
+    /// order they were given.
+    ///
+    /// Legacy IDs in spawn tables are of items, so only roll `asset` if it is one.
+    pub fn spawns_of(&self, asset: &Asset) -> Vec<&'a SpawnAsset> {
+        let spawns: Vec<&'a SpawnAsset> = self
+            .assets
+            .iter()
+            .filter_map(|asset| match asset {
+                Asset::Spawn(spawn) => Some(spawn),
+                _ => None,
+            })
+            .collect();
+        let is_item = AssetCategory::of(asset.base_asset().r#type) == AssetCategory::Item;
+        let mut found = vec![false; spawns.len()];
+        let mut spawn_ids = HashSet::new();
+        let mut spawn_guids = HashSet::new();
+
+        // Spawn assets are found one level further from the asset each pass, until no more are.
+        loop {
+            let mut changed = false;
+
+            for (index, spawn) in spawns.iter().enumerate() {
+                if found[index] {
+                    continue;
+                }
+
+                let rolls = spawn.tables.iter().any(|entry| match entry.target {
+                    SpawnTarget::Asset(id) => is_item && id != 0 && id == asset.id(),
+                    SpawnTarget::Spawn(id) => spawn_ids.contains(&id),
+                    SpawnTarget::Guid(guid) => {
+                        !guid.is_nil() && (guid == asset.guid() || spawn_guids.contains(&guid))
+                    }
+                });
+                if rolls {
+                    found[index] = true;
+                    spawn_ids.insert(spawn.id());
+                    spawn_guids.insert(spawn.guid());
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        spawns
+            .into_iter()
+            .zip(found)
+            .filter(|(_, found)| *found)
+            .map(|(spawn, _)| spawn)
+            .collect()
+    }
+}
@@ -1,7 +1,17 @@
 use std::path::Path;
 
 pub mod assets;
+pub mod blueprint;
+pub mod color;
+pub mod dat;
+pub mod defaults;
+pub mod document;
+pub mod guid;
 pub mod language;
+pub mod level;
+pub mod reference;
+pub mod spawn;
+pub mod vector;
 
 pub trait Parser<T> {
     fn parse<P: AsRef<Path> + ?Sized>(directory: &P, content: &str) -> anyhow::Result<T>;
@@ -2,6 +2,7 @@ pub mod bag;
 pub mod barrel;
 pub mod barricade;
 pub mod caliber;
+pub mod charge;
 pub mod clothing;
 pub mod consumable;
 pub mod gear;
@@ -10,35 +11,150 @@ pub mod grip;
 pub mod gun;
 pub mod magazine;
 pub mod mask;
+pub mod melee;
 pub mod optic;
 pub mod parachute;
 pub mod sentry;
 pub mod shirt;
 pub mod sight;
+pub mod spotlight;
 pub mod storage;
 pub mod structure;
 pub mod tactical;
+pub mod throwable;
 pub mod weapon;
 
 use std::path::Path;
 
-use anyhow::Context;
-
+use crate::parser::blueprint::Blueprint;
+use crate::parser::dat::{
+    self, write_value, DatAsset, DatField, DatValue, Diagnostic, FromDatValue,
+};
+use crate::parser::document;
+use crate::parser::guid::Guid;
 use crate::parser::language::Language;
+use crate::parser::spawn::{self, SpawnAsset};
+use crate::parser::Parser;
+use crate::source::{AssetSource, FileSystem};
 
-use super::Parser;
+use bag::ItemBagAsset;
+use barrel::ItemBarrelAsset;
+use barricade::ItemBarricadeAsset;
+use caliber::ItemCaliberAsset;
+use charge::ItemChargeAsset;
+use clothing::ItemClothingAsset;
+use consumable::ItemConsumableAsset;
+use gear::ItemGearAsset;
+use glasses::ItemGlassesAsset;
+use grip::ItemGripAsset;
+use gun::ItemGunAsset;
+use magazine::ItemMagazineAsset;
+use mask::ItemMaskAsset;
+use melee::ItemMeleeAsset;
+use optic::ItemOpticAsset;
+use parachute::ItemParachuteAsset;
+use sentry::ItemSentryAsset;
+use shirt::ItemShirtAsset;
+use sight::ItemSightAsset;
+use storage::ItemStorageAsset;
+use structure::ItemStructureAsset;
+use tactical::ItemTacticalAsset;
+use throwable::ItemThrowableAsset;
+use weapon::ItemWeaponAsset;
 
-#[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Default, Clone, DatAsset, serde::Deserialize, serde::Serialize)]
+#[dat(
+    finish = read_base,
+    write = write_blueprints,
+    keys = [
+        "Blueprints",
+        "Blueprint_#_Type",
+        "Blueprint_#_Supplies",
+        "Blueprint_#_Supply_#_ID",
+        "Blueprint_#_Supply_#_Amount",
+        "Blueprint_#_Product",
+        "Blueprint_#_Products",
+        "Blueprint_#_Tool",
+        "Blueprint_#_Skill",
+        "Blueprint_#_Level",
+        "Blueprint_#_Outputs",
+        "Blueprint_#_Output_#_ID",
+        "Blueprint_#_Output_#_Amount",
+    ],
+    ignore = [
+        "Size_X",
+        "Size_Y",
+        "Size_Z",
+        "Size2_Z",
+        "Useable",
+        "Slot",
+        "Amount",
+        "Count_Min",
+        "Count_Max",
+        "Quality_Min",
+        "Quality_Max",
+        "Backward",
+        "Pro",
+        "Bypass_ID_Limit",
+        "Bypass_Hash_Verification",
+        "Can_Player_Equip",
+        "Equipable_Movement_Speed_Multiplier",
+        "Should_Delete_At_Zero_Quality",
+        "Allow_Manual_Drop",
+        "Override_Show_Quality",
+        "Exclude_From_Master_Bundle",
+        "Shared_Skin_Lookup_ID",
+        "Use_Auto_Icon_Measurements",
+        "Use_Auto_Stat_Descriptions",
+        "Destroy_Item_Colliders",
+        "Blueprint_#_Supply_#_Critical",
+        "Blueprint_#_Tool_Critical",
+        "Blueprint_#_Build",
+        "Blueprint_#_State_Transfer",
+        "Blueprint_#_Map",
+        "Actions",
+        "Action_#_Type",
+        "Action_#_Source",
+        "Action_#_Key",
+        "Action_#_Text",
+        "Action_#_Tooltip",
+        "Action_#_Blueprints",
+        "Action_#_Blueprint_#_Index",
+        "Action_#_Blueprint_#_Link",
+    ]
+)]
 pub struct BaseAsset {
+    #[dat(skip)]
     pub name: String,
+
+    #[dat(skip)]
     pub description: String,
-    pub guid: String,
+
+    #[dat(key = "GUID")]
+    pub guid: Guid,
+
+    #[dat(key = "Type")]
     pub r#type: Type,
+
+    #[dat(key = "Rarity")]
     pub rarity: Rarity,
+
+    #[dat(key = "ID")]
     pub id: u16,
+
+    /// Crafting recipes of the item, empty for assets that are not items.
+    #[dat(skip)]
+    pub blueprints: Vec<Blueprint>,
+
+    /// If the asset has an `English.dat`, which `name` and `description` are read from.
+    #[dat(skip)]
+    #[serde(skip)]
+    pub localized: bool,
 }
 
-#[derive(Debug, Copy, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Default, DatValue, serde::Serialize, serde::Deserialize,
+)]
 pub enum Type {
     #[default]
     Unknown,
@@ -99,7 +215,7 @@ pub enum Type {
     Animal,
 }
 
-#[derive(Debug, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, Copy, Clone, DatValue, serde::Serialize, serde::Deserialize)]
 pub enum Rarity {
     #[default]
     Common,
@@ -110,116 +226,452 @@ pub enum Rarity {
     Mythical,
 }
 
-impl Parser<BaseAsset> for BaseAsset {
-    fn parse<P: AsRef<Path> + ?Sized>(directory: &P, content: &str) -> anyhow::Result<BaseAsset> {
-        let lines = content.lines();
-        let mut base_asset = BaseAsset::default();
+/// Any asset, since every asset inherits from [`BaseAsset`].
+///
+/// Like the other `As*` traits, this reaches through the parent fields, so
+/// `shirt.name()` is the same as `shirt.item_bag_asset.item_clothing_asset.base_asset.name`.
+pub trait AsBaseAsset {
+    fn base_asset(&self) -> &BaseAsset;
+    fn base_asset_mut(&mut self) -> &mut BaseAsset;
+
+    fn name(&self) -> &str {
+        &self.base_asset().name
+    }
+
+    fn id(&self) -> u16 {
+        self.base_asset().id
+    }
+
+    fn guid(&self) -> Guid {
+        self.base_asset().guid
+    }
+}
+
+/// An asset that inherits from [`ItemWeaponAsset`], such as guns and melee weapons.
+pub trait AsWeapon: AsBaseAsset {
+    fn weapon(&self) -> &ItemWeaponAsset;
+    fn weapon_mut(&mut self) -> &mut ItemWeaponAsset;
+}
+
+/// An asset that inherits from [`ItemCaliberAsset`], such as magazines and attachments.
+pub trait AsCaliber: AsBaseAsset {
+    fn caliber(&self) -> &ItemCaliberAsset;
+    fn caliber_mut(&mut self) -> &mut ItemCaliberAsset;
+}
+
+/// An asset that inherits from [`ItemClothingAsset`], such as shirts and glasses.
+pub trait AsClothing: AsBaseAsset {
+    fn clothing(&self) -> &ItemClothingAsset;
+    fn clothing_mut(&mut self) -> &mut ItemClothingAsset;
+}
+
+/// An asset that inherits from [`ItemBarricadeAsset`], such as storages and charges.
+pub trait AsBarricade: AsBaseAsset {
+    fn barricade(&self) -> &ItemBarricadeAsset;
+    fn barricade_mut(&mut self) -> &mut ItemBarricadeAsset;
+}
+
+/// Implements the given `As*` traits for an asset by asking its parent field.
+macro_rules! inherit {
+    ($($asset:ty => $parent:ident: $($view:ident),+;)*) => {
+        $($(inherit!(@$view $asset, $parent);)+)*
+    };
+    (@AsBaseAsset $asset:ty, $parent:ident) => {
+        impl AsBaseAsset for $asset {
+            fn base_asset(&self) -> &BaseAsset {
+                self.$parent.base_asset()
+            }
+
+            fn base_asset_mut(&mut self) -> &mut BaseAsset {
+                self.$parent.base_asset_mut()
+            }
+        }
+    };
+    (@AsWeapon $asset:ty, $parent:ident) => {
+        impl AsWeapon for $asset {
+            fn weapon(&self) -> &ItemWeaponAsset {
+                self.$parent.weapon()
+            }
+
+            fn weapon_mut(&mut self) -> &mut ItemWeaponAsset {
+                self.$parent.weapon_mut()
+            }
+        }
+    };
+    (@AsCaliber $asset:ty, $parent:ident) => {
+        impl AsCaliber for $asset {
+            fn caliber(&self) -> &ItemCaliberAsset {
+                self.$parent.caliber()
+            }
 
-        for line in lines {
-            let mut split = line.split_whitespace();
+            fn caliber_mut(&mut self) -> &mut ItemCaliberAsset {
+                self.$parent.caliber_mut()
+            }
+        }
+    };
+    (@AsClothing $asset:ty, $parent:ident) => {
+        impl AsClothing for $asset {
+            fn clothing(&self) -> &ItemClothingAsset {
+                self.$parent.clothing()
+            }
 
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
+            fn clothing_mut(&mut self) -> &mut ItemClothingAsset {
+                self.$parent.clothing_mut()
+            }
+        }
+    };
+    (@AsBarricade $asset:ty, $parent:ident) => {
+        impl AsBarricade for $asset {
+            fn barricade(&self) -> &ItemBarricadeAsset {
+                self.$parent.barricade()
+            }
 
-            match field {
-                "GUID" => base_asset.guid = value.into(),
-                "Type" => base_asset.r#type = value.into(),
-                "Rarity" => base_asset.rarity = value.into(),
-                "ID" => base_asset.id = value.parse().context("Failed to parse ID to a u16")?,
-                _ => {}
+            fn barricade_mut(&mut self) -> &mut ItemBarricadeAsset {
+                self.$parent.barricade_mut()
             }
         }
+    };
+}
 
-        let language_file = directory.as_ref().join("English.dat");
+impl AsBaseAsset for BaseAsset {
+    fn base_asset(&self) -> &BaseAsset {
+        self
+    }
 
-        let language = Language::parse_language(&language_file)?;
-        base_asset.name = language.name;
-        base_asset.description = language.description;
+    fn base_asset_mut(&mut self) -> &mut BaseAsset {
+        self
+    }
+}
 
-        Ok(base_asset)
+impl AsWeapon for ItemWeaponAsset {
+    fn weapon(&self) -> &ItemWeaponAsset {
+        self
+    }
+
+    fn weapon_mut(&mut self) -> &mut ItemWeaponAsset {
+        self
+    }
+}
+
+impl AsCaliber for ItemCaliberAsset {
+    fn caliber(&self) -> &ItemCaliberAsset {
+        self
+    }
+
+    fn caliber_mut(&mut self) -> &mut ItemCaliberAsset {
+        self
+    }
+}
+
+impl AsClothing for ItemClothingAsset {
+    fn clothing(&self) -> &ItemClothingAsset {
+        self
+    }
+
+    fn clothing_mut(&mut self) -> &mut ItemClothingAsset {
+        self
     }
 }
 
-impl From<&str> for Rarity {
-    fn from(value: &str) -> Self {
-        match value {
-            "Common" => Self::Common,
-            "Uncommon" => Self::Uncommon,
-            "Rare" => Self::Rare,
-            "Epic" => Self::Epic,
-            "Legendary" => Self::Legendary,
-            "Mythical" => Self::Mythical,
+impl AsBarricade for ItemBarricadeAsset {
+    fn barricade(&self) -> &ItemBarricadeAsset {
+        self
+    }
 
-            "common" => Self::Common,
-            "uncommon" => Self::Uncommon,
-            "rare" => Self::Rare,
-            "epic" => Self::Epic,
-            "legendary" => Self::Legendary,
-            "mythical" => Self::Mythical,
-            _ => panic!("Invalid rarity, got '{}'", value),
+    fn barricade_mut(&mut self) -> &mut ItemBarricadeAsset {
+        self
+    }
+}
+
+inherit! {
+    ItemWeaponAsset => base_asset: AsBaseAsset;
+    ItemCaliberAsset => base_asset: AsBaseAsset;
+    ItemClothingAsset => base_asset: AsBaseAsset;
+    ItemBarricadeAsset => base_asset: AsBaseAsset;
+    ItemStructureAsset => base_asset: AsBaseAsset;
+    ItemOpticAsset => base_asset: AsBaseAsset;
+    ItemParachuteAsset => base_asset: AsBaseAsset;
+    SpawnAsset => base_asset: AsBaseAsset;
+
+    ItemGunAsset => item_weapon_asset: AsBaseAsset, AsWeapon;
+    ItemMeleeAsset => item_weapon_asset: AsBaseAsset, AsWeapon;
+    ItemThrowableAsset => item_weapon_asset: AsBaseAsset, AsWeapon;
+    ItemConsumableAsset => item_weapon_asset: AsBaseAsset, AsWeapon;
+
+    ItemBarrelAsset => item_caliber_asset: AsBaseAsset, AsCaliber;
+    ItemGripAsset => item_caliber_asset: AsBaseAsset, AsCaliber;
+    ItemMagazineAsset => item_caliber_asset: AsBaseAsset, AsCaliber;
+    ItemSightAsset => item_caliber_asset: AsBaseAsset, AsCaliber;
+    ItemTacticalAsset => item_caliber_asset: AsBaseAsset, AsCaliber;
+
+    ItemBagAsset => item_clothing_asset: AsBaseAsset, AsClothing;
+    ItemGearAsset => item_clothing_asset: AsBaseAsset, AsClothing;
+    ItemShirtAsset => item_bag_asset: AsBaseAsset, AsClothing;
+    ItemGlassesAsset => item_gear_asset: AsBaseAsset, AsClothing;
+    ItemMaskAsset => item_gear_asset: AsBaseAsset, AsClothing;
+
+    ItemChargeAsset => item_barricade_asset: AsBaseAsset, AsBarricade;
+    ItemStorageAsset => item_barricade_asset: AsBaseAsset, AsBarricade;
+    ItemSentryAsset => item_storage_asset: AsBaseAsset, AsBarricade;
+}
+
+/// Declares [`Asset`] with a variant per asset class, and the [`Type`]s parsed as each.
+macro_rules! assets {
+    ($($variant:ident($asset:ty) = $kind:literal => $($type:ident)|+;)*) => {
+        /// Any parsed asset, as the class the game reads its [`Type`] as.
+        ///
+        /// Serialized with a `kind` tag next to the fields of the asset, such as
+        /// `{"kind": "gun", "base": {...}, ...}`. Types without a class of their own are
+        /// [`Asset::Other`], holding only the [`BaseAsset`].
+        // Guns are far larger than most assets, but boxing them would only complicate matching.
+        #[allow(clippy::large_enum_variant)]
+        #[derive(Debug, serde::Deserialize, serde::Serialize)]
+        #[serde(tag = "kind")]
+        pub enum Asset {
+            $(
+                #[serde(rename = $kind)]
+                $variant($asset),
+            )*
+            #[serde(rename = "other")]
+            Other(BaseAsset),
         }
+
+        impl Asset {
+            /// Every value of [`kind`](Asset::kind), in declaration order.
+            pub const KINDS: &'static [&'static str] = &[$($kind,)* "other"];
+
+            /// Parses the asset as the class its [`Type`] belongs to, reading the files next to it
+            /// from `source`.
+            pub fn parse_from<P: AsRef<Path> + ?Sized>(
+                source: &dyn AssetSource,
+                directory: &P,
+                content: &str,
+            ) -> anyhow::Result<(Asset, Vec<Diagnostic>)> {
+                let content = document::first_asset(content)?;
+                let directory = directory.as_ref();
+                let mut diagnostics = Vec::new();
+
+                // The last `Type` that parses wins, like when reading the base asset.
+                let r#type = dat::lines(&content)
+                    .filter(|(field, _)| *field == "Type")
+                    .filter_map(|(_, value)| Type::from_dat_value(value).ok())
+                    .last()
+                    .unwrap_or_default();
+
+                let asset = match r#type {
+                    $(
+                        $(Type::$type)|+ => Asset::$variant(
+                            <$asset>::read_dat(source, directory, &content, &mut diagnostics)?,
+                        ),
+                    )*
+                    _ => Asset::Other(
+                        BaseAsset::read_dat(source, directory, &content, &mut diagnostics)?,
+                    ),
+                };
+
+                Ok((asset, diagnostics))
+            }
+
+            /// Every key read by the class that `r#type` is parsed as.
+            pub fn schema_of(r#type: Type) -> Vec<DatField> {
+                match r#type {
+                    $($(Type::$type)|+ => <$asset as DatAsset>::schema(),)*
+                    _ => BaseAsset::schema(),
+                }
+            }
+
+            /// Tag the asset is serialized with, such as `gun`.
+            pub fn kind(&self) -> &'static str {
+                match self {
+                    $(Asset::$variant(_) => $kind,)*
+                    Asset::Other(_) => "other",
+                }
+            }
+        }
+
+        impl AsBaseAsset for Asset {
+            fn base_asset(&self) -> &BaseAsset {
+                match self {
+                    $(Asset::$variant(asset) => asset.base_asset(),)*
+                    Asset::Other(base) => base,
+                }
+            }
+
+            fn base_asset_mut(&mut self) -> &mut BaseAsset {
+                match self {
+                    $(Asset::$variant(asset) => asset.base_asset_mut(),)*
+                    Asset::Other(base) => base,
+                }
+            }
+        }
+    };
+}
+
+assets! {
+    Bag(ItemBagAsset) = "bag" => Backpack | Pants | Vest;
+    Shirt(ItemShirtAsset) = "shirt" => Shirt;
+    Gear(ItemGearAsset) = "gear" => Hat;
+    Glasses(ItemGlassesAsset) = "glasses" => Glasses;
+    Mask(ItemMaskAsset) = "mask" => Mask;
+    Gun(ItemGunAsset) = "gun" => Gun;
+    Melee(ItemMeleeAsset) = "melee" => Melee;
+    Throwable(ItemThrowableAsset) = "throwable" => Throwable;
+    Consumable(ItemConsumableAsset) = "consumable" => Medical;
+    Sight(ItemSightAsset) = "sight" => Sight;
+    Tactical(ItemTacticalAsset) = "tactical" => Tactical;
+    Grip(ItemGripAsset) = "grip" => Grip;
+    Barrel(ItemBarrelAsset) = "barrel" => Barrel;
+    Magazine(ItemMagazineAsset) = "magazine" => Magazine;
+    Optic(ItemOpticAsset) = "optic" => Optic;
+    Parachute(ItemParachuteAsset) = "parachute" => Cloud;
+    Barricade(ItemBarricadeAsset) = "barricade" => Barricade;
+    Charge(ItemChargeAsset) = "charge" => Charge;
+    Storage(ItemStorageAsset) = "storage" => Storage;
+    Sentry(ItemSentryAsset) = "sentry" => Sentry;
+    Structure(ItemStructureAsset) = "structure" => Structure;
+    Spawn(SpawnAsset) = "spawn" => Spawn;
+}
+
+/// A parsed asset and what was ignored while reading it, or why it could not be parsed.
+pub type AssetResult = anyhow::Result<(Asset, Vec<Diagnostic>)>;
+
+impl Asset {
+    /// Parses every asset of a data file, in the order of [`document::asset_blocks`]. An asset
+    /// that fails to parse does not stop the others.
+    ///
+    /// Unlike parsing each block on its own, this also reads the `Tables` of spawn assets in the
+    /// v2 format, which are nested and so left out of the blocks.
+    pub fn parse_all(
+        source: &dyn AssetSource,
+        directory: &Path,
+        content: &str,
+    ) -> anyhow::Result<Vec<AssetResult>> {
+        let blocks = document::asset_blocks(content)?;
+        let mut tables = spawn::nested_tables(content)?.into_iter();
+
+        Ok(blocks
+            .iter()
+            .map(|block| {
+                let tables = tables.next().flatten();
+                let (mut asset, diagnostics) = Asset::parse_from(source, directory, block)?;
+                if let (Asset::Spawn(spawn), Some(tables)) = (&mut asset, tables) {
+                    spawn.tables = tables;
+                }
+
+                Ok((asset, diagnostics))
+            })
+            .collect())
+    }
+}
+
+impl Parser<Asset> for Asset {
+    fn parse<P: AsRef<Path> + ?Sized>(directory: &P, content: &str) -> anyhow::Result<Asset> {
+        Asset::parse_from(&FileSystem, directory, content).map(|(asset, _)| asset)
+    }
+}
+
+/// Reads the name and description, see [`read_language`], and the blueprints of the item.
+fn read_base(
+    item: &mut BaseAsset,
+    source: &dyn AssetSource,
+    directory: &Path,
+    content: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    read_language(item, source, directory)?;
+    item.blueprints = Blueprint::read_blueprints(content, item.id, diagnostics)?;
+
+    Ok(())
+}
+
+fn write_blueprints(item: &BaseAsset, out: &mut String) {
+    Blueprint::write_list(&item.blueprints, out);
+}
+
+/// Reads the name and description from the `English.dat` next to the asset.
+///
+/// Assets without one, such as most `.asset` files, keep an empty name like in the game, and are
+/// not [`localized`](BaseAsset::localized).
+fn read_language(
+    item: &mut BaseAsset,
+    source: &dyn AssetSource,
+    directory: &Path,
+) -> anyhow::Result<()> {
+    let language_file = directory.join("English.dat");
+    if !source.is_file(&language_file) {
+        return Ok(());
     }
+
+    let language = Language::parse_language(source, &language_file)?;
+    item.name = language.name;
+    item.description = language.description;
+    item.localized = true;
+
+    Ok(())
 }
 
-impl From<&str> for Type {
-    fn from(value: &str) -> Self {
-        match value {
-            "Hat" => Self::Hat,
-            "Pants" => Self::Pants,
-            "Shirt" => Self::Shirt,
-            "Mask" => Self::Mask,
-            "Backpack" => Self::Backpack,
-            "Vest" => Self::Vest,
-            "Glasses" => Self::Glasses,
-            "Gun" => Self::Gun,
-            "Sight" => Self::Sight,
-            "Tactical" => Self::Tactical,
-            "Grip" => Self::Grip,
-            "Barrel" => Self::Barrel,
-            "Magazine" => Self::Magazine,
-            "Food" => Self::Food,
-            "Water" => Self::Water,
-            "Medical" => Self::Medical,
-            "Melee" => Self::Melee,
-            "Fuel" => Self::Fuel,
-            "Tool" => Self::Tool,
-            "Barricade" => Self::Barricade,
-            "Storage" => Self::Storage,
-            "Beacon" => Self::Beacon,
-            "Farm" => Self::Farm,
-            "Trap" => Self::Trap,
-            "Structure" => Self::Structure,
-            "Supply" => Self::Supply,
-            "Throwable" => Self::Throwable,
-            "Grower" => Self::Grower,
-            "Optic" => Self::Optic,
-            "Refill" => Self::Refill,
-            "Fisher" => Self::Fisher,
-            "Cloud" => Self::Cloud,
-            "Map" => Self::Map,
-            "Key" => Self::Key,
-            "Box" => Self::Box,
-            "Arrest_Start" => Self::ArrestStart,
-            "Arrest_End" => Self::ArrestEnd,
-            "Tank" => Self::Tank,
-            "Generator" => Self::Generator,
-            "Detonator" => Self::Detonator,
-            "Charge" => Self::Charge,
-            "Library" => Self::Library,
-            "Filter" => Self::Filter,
-            "Sentry" => Self::Sentry,
-            "Vehicle_Repair_Tool" => Self::VehicleRepairTool,
-            "Tire" => Self::Tire,
-            "Compass" => Self::Compass,
-            "Oil_Pump" => Self::OilPump,
-            "Resource" => Self::Resource,
-            "Vehicle" => Self::Vehicle,
-            "Spawn" => Self::Spawn,
-            "Npc" => Self::Npc,
-            "Decal" => Self::Decal,
-            "Effect" => Self::Effect,
-            "Animal" => Self::Animal,
-            _ => Self::Unknown,
+/// Reads an indexed list of IDs, such as:
+///
+/// ```text
+/// Calibers 2
+/// Caliber_0 1
+/// Caliber_1 2
+/// ```
+///
+/// Only the first `<count_key>` entries are read, like the game does. Values that do not parse
+/// are reported to `diagnostics` and left out.
+pub(crate) fn parse_id_list(
+    content: &str,
+    count_key: &str,
+    prefix: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<Vec<u16>> {
+    let mut count = 0;
+    let mut ids: Vec<(usize, u16)> = Vec::new();
+
+    for line in content.lines() {
+        let mut split = line.split_whitespace();
+
+        let field = split.next().unwrap_or("");
+        let value = split.next().unwrap_or("");
+
+        if field == count_key {
+            if let Some(value) = dat::read_or_report(field, value, diagnostics)? {
+                count = value;
+            }
+            continue;
+        }
+
+        let Some(index) = field
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .and_then(|index| index.parse::<usize>().ok())
+        else {
+            continue;
+        };
+
+        if let Some(id) = dat::read_or_report(field, value, diagnostics)? {
+            ids.push((index, id));
         }
     }
+
+    ids.sort_by_key(|(index, _)| *index);
+
+    Ok(ids
+        .into_iter()
+        .filter(|(index, _)| *index < count)
+        .map(|(_, id)| id)
+        .collect())
+}
+
+/// Writes an indexed list of IDs, as read by [`parse_id_list`].
+pub(crate) fn write_id_list(out: &mut String, count_key: &str, prefix: &str, ids: &[u16]) {
+    if ids.is_empty() {
+        return;
+    }
+
+    write_value(out, count_key, &ids.len());
+    for (index, id) in ids.iter().enumerate() {
+        write_value(out, &format!("{}_{}", prefix, index), id);
+    }
 }
@@ -1,67 +1,38 @@
-use anyhow::Context;
+use crate::parser::dat::{DatAsset, DatValue};
 
 use super::BaseAsset;
-use super::Parser;
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct ItemBarricadeAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub base_asset: BaseAsset,
 
+    #[dat(key = "Health")]
     pub health: u16,
+
+    #[dat(key = "Locked", flag)]
     pub is_locked: bool,
+
+    #[dat(key = "Vulnerable", flag)]
     pub is_vulnerable: bool,
+
+    #[dat(key = "Bypass_Claim", flag)]
     pub can_bypass_claims: bool,
+
+    #[dat(key = "Range")]
     pub range: f32,
+
+    #[dat(key = "Radius")]
     pub radius: f32,
+
+    #[dat(key = "Armor_Tier")]
     pub armor_tier: ArmorTier,
 }
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatValue, serde::Serialize, serde::Deserialize)]
 pub enum ArmorTier {
     #[default]
     Low,
     High,
 }
-
-impl Parser<ItemBarricadeAsset> for ItemBarricadeAsset {
-    fn parse<P: AsRef<std::path::Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemBarricadeAsset> {
-        let base_asset = BaseAsset::parse(directory, content)?;
-        let mut item = ItemBarricadeAsset {
-            base_asset,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Health" => item.health = value.parse().context("Failed to parse Health as u16")?,
-                "Locked" => item.is_locked = true,
-                "Vulnerable" => item.is_vulnerable = true,
-                "Bypass_Claim" => item.can_bypass_claims = true,
-                "Range" => item.range = value.parse().context("Failed to parse Range as f32")?,
-                "Radius" => item.radius = value.parse().context("Failed to parse Radius as f32")?,
-                "Armor_Tier" => item.armor_tier = value.into(),
-                _ => {}
-            }
-        }
-
-        Ok(item)
-    }
-}
-
-impl From<&str> for ArmorTier {
-    fn from(value: &str) -> Self {
-        match value {
-            "High" => Self::High,
-            _ => Self::Low,
-        }
-    }
-}
@@ -1,124 +1,110 @@
 use std::path::Path;
 
-use anyhow::Context;
+use crate::parser::dat::{self, DatAsset, Diagnostic};
+use crate::source::AssetSource;
 
-use super::{BaseAsset, Parser};
+use super::{parse_id_list, write_id_list, BaseAsset};
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+#[dat(
+    finish = read_calibers,
+    write = write_calibers,
+    keys = ["Calibers", "Caliber", "Caliber_#"]
+)]
 pub struct ItemCaliberAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub base_asset: BaseAsset,
 
     /// Multiplier on character movement speed while aiming down sights.
+    #[dat(key = "Aiming_Movement_Speed_Multiplier", default = 1.0)]
     pub aiming_movement_speed_multiplier: f32,
 
     /// Multiplier on recoil magnitude while aiming down sights.
+    #[dat(key = "Aiming_Recoil_Multiplier", default = 1.0)]
     pub aiming_recoil_multiplier: f32,
 
     /// Multiplier on the value of `aim_in_duration` property available to `ItemGunAsset`.
+    #[dat(key = "Aim_Duration_Multiplier", default = 1.0)]
     pub aim_duration_multiplier: f32,
 
     /// Multiplier on damage.
     ///
     /// Defaults to the value of the Damage property, or 1 if both properties are unset.
+    #[dat(
+        key = "Ballistic_Damage_Multiplier",
+        default = if item.damage != 0.0 { item.damage } else { 1.0 }
+    )]
     pub ballistic_damage_multiplier: f32,
 
+    /// Calibers the attachment belongs to, matched against the gun's `attachment_calibers`
+    /// or `magazine_calibers`.
+    #[dat(skip)]
     pub calibers: Vec<u16>,
 
     /// *Deprecated since version 3.27.0.0: Use `ballistic_damage_multiplier` instead.*
     ///
     /// Maintained for backwards compatibility.
     /// If both this property and Ballistic_Damage_Multiplier have been set, the latter’s value is used.
+    #[dat(key = "Damage")]
     pub damage: f32,
 
     /// The value of the attached ranged weapon’s Firerate property is reduced by the value of this property.
     ///
     /// A larger decrease will allow for the ranged weapon to fire more often.
+    #[dat(key = "Firerate")]
     pub firerate: i8,
 
     /// If true, gun can damage entities with Invulnerable tag.
+    #[dat(key = "Invulnerable", flag)]
     pub invulnerable: bool,
 
     /// When this flag is included, the attachment should be affected by Steam Economy skins that include support for skinning attachments.
+    #[dat(key = "Paintable", flag)]
     pub paintable: bool,
 
     /// Multiplier on horizontal recoil.
+    #[dat(key = "Recoil_X", default = 1.0)]
     pub recoil_x: f32,
 
     /// Multiplier on vertical recoil.
+    #[dat(key = "Recoil_Y", default = 1.0)]
     pub recoil_y: f32,
 
     /// Multiplier on shake.
+    #[dat(key = "Shake", default = 1.0)]
     pub shake: f32,
 
     /// Multiplier on bullet spread.
+    #[dat(key = "Spread", default = 1.0)]
     pub spread: f32,
 
     /// Multiplier on scope sway.
+    #[dat(key = "Sway", default = 1.0)]
     pub sway: f32,
 }
 
-impl Parser<ItemCaliberAsset> for ItemCaliberAsset {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemCaliberAsset> {
-        let base_asset = BaseAsset::parse(directory, content)?;
-        let mut item = ItemCaliberAsset {
-            base_asset,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Aiming_Movement_Speed_Multiplier" => {
-                    item.aiming_movement_speed_multiplier = value
-                        .parse()
-                        .context("Failed to parse Aiming_Movement_Speed_Multiplier as a f32")?
-                }
-                "Aiming_Recoil_Multiplier" => {
-                    item.aiming_recoil_multiplier = value
-                        .parse()
-                        .context("Failed to parse Aiming_Recoil_Multiplier as a f32")?
-                }
-                "Aim_Duration_Multiplier" => {
-                    item.aiming_recoil_multiplier = value
-                        .parse()
-                        .context("Failed to parse Aim_Duration_Multiplier as a f32")?
-                }
-                "Ballistic_Damage_Multiplier" => {
-                    item.aiming_recoil_multiplier = value
-                        .parse()
-                        .context("Failed to parse Ballistic_Damage_Multiplier as a f32")?
-                }
-                "Damage" => {
-                    item.damage = value.parse().context("Failed to parse Damage as a f32")?
-                }
-                "Firerate" => {
-                    item.firerate = value.parse().context("Failed to parse Firerate as a i8")?;
-                }
-                "Invulnerable" => item.invulnerable = true,
-                "Paintable" => item.paintable = true,
-                "Recoil_X" => {
-                    item.recoil_x = value.parse().context("Failed to parse Recoil_X as a f32")?
-                }
-                "Recoil_Y" => {
-                    item.recoil_y = value.parse().context("Failed to parse Recoil_Y as a f32")?
-                }
-                "Shake" => item.shake = value.parse().context("Failed to parse Shake as a f32")?,
-                "Spread" => {
-                    item.spread = value.parse().context("Failed to parse Spread as a f32")?
-                }
-                "Sway" => item.sway = value.parse().context("Failed to parse Sway as f32")?,
-                _ => {}
+/// Reads the `Calibers` list, or the legacy single `Caliber` without one.
+fn read_calibers(
+    item: &mut ItemCaliberAsset,
+    _source: &dyn AssetSource,
+    _directory: &Path,
+    content: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    item.calibers = parse_id_list(content, "Calibers", "Caliber", diagnostics)?;
+
+    if item.calibers.is_empty() {
+        if let Some((field, value)) = dat::lines(content).find(|(field, _)| *field == "Caliber") {
+            if let Some(caliber) = dat::read_or_report(field, value, diagnostics)? {
+                item.calibers.push(caliber);
             }
         }
-
-        Ok(item)
     }
+
+    Ok(())
+}
+
+fn write_calibers(item: &ItemCaliberAsset, out: &mut String) {
+    write_id_list(out, "Calibers", "Caliber", &item.calibers);
 }
@@ -0,0 +1,52 @@
+use crate::parser::dat::DatAsset;
+
+use super::barricade::ItemBarricadeAsset;
+
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct ItemChargeAsset {
+    #[serde(rename = "base")]
+    #[dat(flatten)]
+    pub item_barricade_asset: ItemBarricadeAsset,
+
+    /// In meters, the radius of the explosion.
+    #[dat(key = "Range2")]
+    pub explosion_range: f32,
+
+    /// Damage dealt to players caught within the explosion.
+    #[dat(key = "Player_Damage")]
+    pub player_damage: f32,
+
+    /// Damage dealt to zombies caught within the explosion.
+    #[dat(key = "Zombie_Damage")]
+    pub zombie_damage: f32,
+
+    /// Damage dealt to animals caught within the explosion.
+    #[dat(key = "Animal_Damage")]
+    pub animal_damage: f32,
+
+    /// Damage dealt to barricades caught within the explosion.
+    #[dat(key = "Barricade_Damage")]
+    pub barricade_damage: f32,
+
+    /// Damage dealt to structures caught within the explosion.
+    #[dat(key = "Structure_Damage")]
+    pub structure_damage: f32,
+
+    /// Damage dealt to vehicles caught within the explosion.
+    #[dat(key = "Vehicle_Damage")]
+    pub vehicle_damage: f32,
+
+    /// Damage dealt to resources caught within the explosion.
+    #[dat(key = "Resource_Damage")]
+    pub resource_damage: f32,
+
+    /// Damage dealt to objects caught within the explosion.
+    ///
+    /// Defaults to the value of `resource_damage`.
+    #[dat(key = "Object_Damage", default = item.resource_damage)]
+    pub object_damage: f32,
+
+    /// ID of the effect played when detonating.
+    #[dat(key = "Explosion2")]
+    pub explosion: u16,
+}
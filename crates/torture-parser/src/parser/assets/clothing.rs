@@ -1,61 +1,47 @@
-use anyhow::Context;
+use crate::parser::dat::DatAsset;
 
-use super::{BaseAsset, Parser};
+use super::BaseAsset;
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct ItemClothingAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub base_asset: BaseAsset,
 
+    /// Multiplier on damage taken by the body part the clothing covers.
+    ///
+    /// Defaults to 1.
+    #[dat(key = "Armor", default = 1.0)]
     pub armor: f32,
+
+    /// Multiplier on explosion damage taken by the body part the clothing covers.
+    ///
+    /// Defaults to the value of `armor`.
+    #[dat(key = "Armor_Explosion", default = item.armor)]
     pub armor_explosion: f32,
 
+    #[dat(key = "Proof_Water", flag)]
     pub water_proof: bool,
+    #[dat(key = "Proof_Fire", flag)]
     pub fire_proof: bool,
+    #[dat(key = "Proof_Radiation", flag)]
     pub radiation_proof: bool,
+
+    /// Multiplier on the speed of the character wearing the clothing.
+    ///
+    /// Defaults to 1.
+    #[dat(key = "Movement_Speed_Multiplier", default = 1.0)]
     pub movement_speed_multiplier: f32,
+
+    /// Defaults to true.
+    #[dat(key = "Visible_On_Ragdoll", default = true)]
     pub visible_on_ragdoll: bool,
+
+    /// Defaults to true.
+    #[dat(key = "Hair_Visible", default = true)]
     pub hair_visible: bool,
-    pub beard_visible: bool,
-}
 
-impl Parser<ItemClothingAsset> for ItemClothingAsset {
-    fn parse<P: AsRef<std::path::Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemClothingAsset> {
-        let base_asset = BaseAsset::parse(directory, content)?;
-        let mut item = ItemClothingAsset {
-            base_asset,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Armor" => item.armor = value.parse().context("Failed to parse Armor as f32")?,
-                "Armor_Explosion" => {
-                    item.armor_explosion = value
-                        .parse()
-                        .context("Failed to parse Armor_Explosion as f32")?
-                }
-                "Proof_Water" => item.water_proof = true,
-                "Proof_Fire" => item.fire_proof = true,
-                "Proof_Radiation" => item.radiation_proof = true,
-                "Movement_Speed_Multiplier" => {
-                    item.movement_speed_multiplier = value.parse().unwrap_or(1.0)
-                }
-                "Visible_On_Ragdoll" => item.visible_on_ragdoll = value.parse().unwrap_or(true),
-                "Hair_Visible" => item.hair_visible = value.parse().unwrap_or(true),
-                "Beard_Visible" => item.beard_visible = value.parse().unwrap_or(true),
-                _ => {}
-            }
-        }
-
-        Ok(item)
-    }
+    /// Defaults to true.
+    #[dat(key = "Beard_Visible", default = true)]
+    pub beard_visible: bool,
 }
@@ -1,63 +1,71 @@
+use std::path::Path;
+
+use crate::parser::color::Color;
+use crate::parser::dat::{DatAsset, Diagnostic};
+use crate::source::AssetSource;
+
 use super::gear::ItemGearAsset;
-use super::Parser;
+use super::spotlight::SpotLightConfig;
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub use super::sight::Vision;
+
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+#[dat(finish = read_spotlight, write = write_spotlight)]
 pub struct ItemGlassesAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub item_gear_asset: ItemGearAsset,
 
+    #[dat(key = "Vision")]
     pub vision: Vision,
+    #[dat(key = "Blindfold", flag)]
     pub is_blindfold: bool,
+
+    /// Color of the nightvision lighting.
+    ///
+    /// Defaults depend on `vision`, see [`Vision`].
+    #[dat(
+        key = "Nightvision_Color",
+        legacy_rgb,
+        default = item.vision.nightvision_defaults().0
+    )]
+    pub nightvision_color: Color,
+
+    /// Intensity of the nightvision fog.
+    ///
+    /// Defaults depend on `vision`, see [`Vision`].
+    #[dat(
+        key = "Nightvision_Fog_Intensity",
+        default = item.vision.nightvision_defaults().1
+    )]
     pub nightvision_fog_intensity: f32,
+
+    /// Light source properties, present when `vision` is [`Vision::Headlamp`].
+    #[dat(skip, schema = SpotLightConfig)]
+    pub spotlight: Option<SpotLightConfig>,
 }
 
-impl Parser<ItemGlassesAsset> for ItemGlassesAsset {
-    fn parse<P: AsRef<std::path::Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemGlassesAsset> {
-        let item_gear_asset = ItemGearAsset::parse(directory, content)?;
-        let mut item = ItemGlassesAsset {
-            item_gear_asset,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Vision" => item.vision = value.into(),
-                "Blindfold" => item.is_blindfold = true,
-                "Nightvision_Fog_Intensity" => {
-                    item.nightvision_fog_intensity = value.parse().unwrap_or_default()
-                }
-                _ => {}
-            }
-        }
-
-        Ok(item)
+fn read_spotlight(
+    item: &mut ItemGlassesAsset,
+    source: &dyn AssetSource,
+    directory: &Path,
+    content: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    if let Vision::Headlamp = item.vision {
+        item.spotlight = Some(SpotLightConfig::read_dat(
+            source,
+            directory,
+            content,
+            diagnostics,
+        )?);
     }
-}
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
-pub enum Vision {
-    #[default]
-    None,
-    Military,
-    Civilian,
-    Headlamp,
+    Ok(())
 }
 
-impl From<&str> for Vision {
-    fn from(value: &str) -> Self {
-        match value {
-            "Military" => Self::Military,
-            "Civilian" => Self::Civilian,
-            "Headlamp" => Self::Headlamp,
-            _ => Self::None,
-        }
+fn write_spotlight(item: &ItemGlassesAsset, out: &mut String) {
+    if let Some(spotlight) = &item.spotlight {
+        spotlight.write_dat(out);
     }
 }
@@ -1,46 +1,263 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 
-use anyhow::Context;
-
+use super::barrel::ItemBarrelAsset;
+use super::grip::ItemGripAsset;
+use super::magazine::ItemMagazineAsset;
+use super::sight::ItemSightAsset;
+use super::tactical::ItemTacticalAsset;
 use super::weapon::ItemWeaponAsset;
-use super::Parser;
-
-// TODO: DOCUMENT THIS :sob:
-
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+use super::{parse_id_list, write_id_list};
+use crate::parser::dat::{self, write_flag, write_value, DatAsset, DatValue, Diagnostic};
+use crate::parser::reference::AssetReference;
+use crate::parser::Parser;
+use crate::source::AssetSource;
+
+/// A gun, with the attachments it spawns with, the attachments it accepts, and how it fires.
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+#[dat(
+    finish = read_lists,
+    write = write_lists,
+    keys = [
+        "Hook_Sight",
+        "Hook_Tactical",
+        "Hook_Grip",
+        "Hook_Barrel",
+        "Safety",
+        "Semi",
+        "Auto",
+        "Burst",
+        "Magazine_Replacements",
+        "Magazine_Replacement_#_Map",
+        "Magazine_Replacement_#_ID",
+        "Caliber",
+        "Magazine_Calibers",
+        "Magazine_Caliber_#",
+        "Attachment_Calibers",
+        "Attachment_Caliber_#",
+    ]
+)]
 pub struct ItemGunAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub item_weapon_asset: ItemWeaponAsset,
 
+    #[dat(flatten)]
     pub ammo: Ammo,
-    pub sight_id: u16,
-    pub tactical_id: u16,
-    pub grip_id: u16,
-    pub barrel_id: u16,
-    pub magazine_id: u16,
-    // pub magazine_replacements: Vec<MagazineReplacement>,
+
+    /// Sight the gun spawns with.
+    #[dat(key = "Sight")]
+    pub sight_id: AssetReference<ItemSightAsset>,
+
+    /// Tactical attachment the gun spawns with.
+    #[dat(key = "Tactical")]
+    pub tactical_id: AssetReference<ItemTacticalAsset>,
+
+    /// Grip the gun spawns with.
+    #[dat(key = "Grip")]
+    pub grip_id: AssetReference<ItemGripAsset>,
+
+    /// Barrel the gun spawns with.
+    #[dat(key = "Barrel")]
+    pub barrel_id: AssetReference<ItemBarrelAsset>,
+
+    /// Magazine the gun spawns with.
+    #[dat(key = "Magazine")]
+    pub magazine_id: AssetReference<ItemMagazineAsset>,
+
+    /// Magazines the gun spawns with instead on specific maps.
+    #[dat(skip)]
+    pub magazine_replacements: Vec<MagazineReplacement>,
+
+    /// Attachment slots the gun has.
+    #[dat(skip)]
     pub hooks: Vec<Hook>,
-    // pub magazine_calibers: Vec<u16>,
-    // pub attachment_calibers: Vec<u16>,
+
+    /// Calibers of the magazines the gun accepts.
+    #[dat(skip)]
+    pub magazine_calibers: Vec<u16>,
+
+    /// Calibers of the sights, tacticals, grips and barrels the gun accepts.
+    #[dat(skip)]
+    pub attachment_calibers: Vec<u16>,
+
+    /// Ticks between two shots.
+    #[dat(key = "Firerate")]
     pub firerate: u8,
+
+    /// How the gun is operated, which decides its animations and sounds.
+    #[dat(key = "Action")]
     pub action: Action,
+
+    /// Fire modes the player can switch between.
+    #[dat(skip)]
     pub firemodes: Vec<Firemode>,
+
+    /// If the gun is mounted on a vehicle as a turret.
+    #[dat(key = "Turret", flag)]
     pub is_turret: bool,
+
+    #[dat(flatten)]
+    pub spread: Spread,
+    #[dat(flatten)]
+    pub recoil: Recoil,
+    #[dat(flatten)]
+    pub shake: Shake,
+    #[dat(flatten)]
+    pub ballistics: Ballistics,
+    #[dat(flatten)]
+    pub damage_falloff: DamageFalloff,
+
+    /// Seconds it takes to aim down sights.
+    ///
+    /// Defaults to 0.2.
+    #[dat(key = "Aim_In_Duration", default = 0.2)]
+    pub aim_in_duration: f32,
+
+    /// If the gun can stay aimed down sights while sprinting.
+    #[dat(key = "Can_Aim_During_Sprint")]
+    pub can_aim_during_sprint: bool,
+
+    /// Seconds it takes to reload.
+    #[dat(key = "Reload_Time")]
+    pub reload_time: f32,
+
+    /// Seconds it takes to chamber the next round after reloading, e.g. racking a shotgun.
+    #[dat(key = "Hammer_Timer")]
+    pub hammer_timer: f32,
+
+    /// Seconds before a physics projectile (e.g. a rocket) despawns.
+    ///
+    /// Defaults to 30.
+    #[dat(key = "Projectile_Lifespan", default = 30.0)]
+    pub projectile_lifespan: f32,
+
+    /// Launch speed of physics projectiles.
+    #[dat(key = "Projectile_Explosion_Launch_Speed")]
+    pub projectile_explosion_launch_speed: f32,
+
+    /// Radius around the shooter that zombies and animals are alerted in, in meters.
+    ///
+    /// Defaults to 48.
+    #[dat(key = "Alert_Radius", default = 48.0)]
+    pub alert_radius: f32,
 }
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct Ammo {
+    #[dat(key = "Ammo_Min")]
     pub min: u8,
+    #[dat(key = "Ammo_Max")]
     pub max: u8,
 }
 
-// #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
-// pub struct MagazineReplacement {
-//     pub map: String,
-//     pub id: u16,
-// }
+/// Bullet spread, as the tangent of the cone angle.
+#[derive(Debug, Default, Clone, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct Spread {
+    /// Spread while aiming down sights.
+    #[dat(key = "Spread_Aim")]
+    pub aim: f32,
+
+    /// Spread while firing from the hip.
+    #[dat(key = "Spread_Hip")]
+    pub hip: f32,
 
+    /// Multiplier on spread while sprinting.
+    #[dat(key = "Spread_Sprint", default = 1.25)]
+    pub sprint: f32,
+
+    /// Multiplier on spread while crouching.
+    #[dat(key = "Spread_Crouch", default = 0.85)]
+    pub crouch: f32,
+
+    /// Multiplier on spread while prone.
+    #[dat(key = "Spread_Prone", default = 0.7)]
+    pub prone: f32,
+}
+
+/// Camera recoil applied after each shot, in degrees.
+///
+/// Each shot picks a random value between the minimum and maximum of each axis.
+#[derive(Debug, Default, Clone, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct Recoil {
+    #[dat(key = "Recoil_Min_X")]
+    pub min_x: f32,
+    #[dat(key = "Recoil_Max_X")]
+    pub max_x: f32,
+    #[dat(key = "Recoil_Min_Y")]
+    pub min_y: f32,
+    #[dat(key = "Recoil_Max_Y")]
+    pub max_y: f32,
+
+    /// Multiplier on recoil while aiming down sights.
+    #[dat(key = "Recoil_Aim", default = 1.0)]
+    pub aim: f32,
+}
+
+/// Viewmodel shake applied after each shot.
+#[derive(Debug, Default, Clone, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct Shake {
+    #[dat(key = "Shake_Min_X")]
+    pub min_x: f32,
+    #[dat(key = "Shake_Min_Y")]
+    pub min_y: f32,
+    #[dat(key = "Shake_Min_Z")]
+    pub min_z: f32,
+    #[dat(key = "Shake_Max_X")]
+    pub max_x: f32,
+    #[dat(key = "Shake_Max_Y")]
+    pub max_y: f32,
+    #[dat(key = "Shake_Max_Z")]
+    pub max_z: f32,
+}
+
+/// Properties of bullets simulated by ballistics.
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct Ballistics {
+    /// Number of steps a bullet is simulated for before it despawns.
+    ///
+    /// If unset, this is enough steps to travel the weapon's range.
+    #[dat(key = "Ballistic_Steps")]
+    pub steps: u8,
+
+    /// Distance a bullet travels each step, in meters.
+    #[dat(key = "Ballistic_Travel", default = 10.0)]
+    pub travel: f32,
+
+    /// Gravity acceleration applied to bullets each step.
+    #[dat(key = "Ballistic_Drop", default = 0.002)]
+    pub drop: f32,
+
+    /// Force applied to physics objects that are hit.
+    #[dat(key = "Ballistic_Force", default = 0.002)]
+    pub force: f32,
+}
+
+/// Damage reduction over distance.
+///
+/// Damage starts falling off at `range` and reaches `multiplier` at `max_range`,
+/// both of which are a fraction (0 to 1) of the weapon's range.
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct DamageFalloff {
+    #[dat(key = "Damage_Falloff_Range", default = 1.0)]
+    pub range: f32,
+    #[dat(key = "Damage_Falloff_Max_Range", default = 1.0)]
+    pub max_range: f32,
+    #[dat(key = "Damage_Falloff_Multiplier", default = 1.0)]
+    pub multiplier: f32,
+}
+
+/// Overrides the default magazine of a gun on a specific map.
 #[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct MagazineReplacement {
+    /// Name of the map, as in [`Level::name`](crate::parser::level::Level::name).
+    pub map: String,
+
+    /// Magazine used instead of `magazine_id` on that map.
+    pub id: AssetReference<ItemMagazineAsset>,
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Hook {
     #[default]
     None, // will literally never be used.
@@ -50,7 +267,7 @@ pub enum Hook {
     Barrel,
 }
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatValue, serde::Serialize, serde::Deserialize)]
 pub enum Action {
     #[default]
     None, // will literally never be used.
@@ -74,65 +291,106 @@ pub enum Firemode {
     Burst(i32),
 }
 
-impl Parser<ItemGunAsset> for ItemGunAsset {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemGunAsset> {
-        let item_weapon_asset = ItemWeaponAsset::parse(directory, content)?;
-        let ammo = Ammo::parse(directory, content)?;
-        let hooks: Vec<Hook> = Hook::parse(directory, content)?;
-        let firemodes: Vec<Firemode> = Firemode::parse(directory, content)?;
-
-        let mut item = ItemGunAsset {
-            item_weapon_asset,
-            ammo,
-            hooks,
-            firemodes,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Barrel" => item.barrel_id = value.parse().unwrap_or(0),
-                "Grip" => item.grip_id = value.parse().unwrap_or(0),
-                "Sight" => item.sight_id = value.parse().unwrap_or(0),
-                "Tactical" => item.tactical_id = value.parse().unwrap_or(0),
-                "Magazine" => item.magazine_id = value.parse().unwrap_or(0),
-                "Firerate" => item.firerate = value.parse().unwrap_or(0),
-                "Action" => item.action = value.into(),
-                "Turret" => item.is_turret = true,
-                _ => {}
+fn read_lists(
+    item: &mut ItemGunAsset,
+    _source: &dyn AssetSource,
+    directory: &Path,
+    content: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    item.hooks = Hook::parse(directory, content)?;
+    item.firemodes = Firemode::read_firemodes(content, diagnostics)?;
+    item.magazine_replacements = MagazineReplacement::read_replacements(content, diagnostics)?;
+    item.magazine_calibers = parse_id_list(
+        content,
+        "Magazine_Calibers",
+        "Magazine_Caliber",
+        diagnostics,
+    )?;
+    item.attachment_calibers = parse_id_list(
+        content,
+        "Attachment_Calibers",
+        "Attachment_Caliber",
+        diagnostics,
+    )?;
+
+    // Legacy single caliber, shared by magazines and attachments.
+    if let Some((field, value)) = dat::lines(content).find(|(field, _)| *field == "Caliber") {
+        if let Some(caliber) = dat::read_or_report(field, value, diagnostics)? {
+            if item.magazine_calibers.is_empty() {
+                item.magazine_calibers.push(caliber);
+            }
+            if item.attachment_calibers.is_empty() {
+                item.attachment_calibers.push(caliber);
             }
         }
+    }
 
-        Ok(item)
+    let ballistics = &mut item.ballistics;
+    if ballistics.steps == 0 && ballistics.travel > 0.0 {
+        let steps = (item.item_weapon_asset.range / ballistics.travel).ceil();
+        ballistics.steps = steps.clamp(0.0, u8::MAX as f32) as u8;
     }
-}
 
-impl Parser<Ammo> for Ammo {
-    fn parse<P: AsRef<Path> + ?Sized>(_directory: &P, content: &str) -> anyhow::Result<Ammo> {
-        let mut ammo = Ammo::default();
+    Ok(())
+}
 
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
+fn write_lists(item: &ItemGunAsset, out: &mut String) {
+    for hook in &item.hooks {
+        write_flag(out, &format!("Hook_{:?}", hook));
+    }
 
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
+    for firemode in &item.firemodes {
+        match firemode {
+            Firemode::None => {}
+            Firemode::Burst(amount) => write_value(out, "Burst", amount),
+            firemode => write_flag(out, &format!("{:?}", firemode)),
+        }
+    }
 
-            match field {
-                "Ammo_Min" => ammo.min = value.parse().context("Failed to parse Ammo_Min as u8")?,
-                "Ammo_Max" => ammo.max = value.parse().context("Failed to parse Ammo_Max as u8")?,
-                _ => {}
-            }
+    write_id_list(
+        out,
+        "Magazine_Calibers",
+        "Magazine_Caliber",
+        &item.magazine_calibers,
+    );
+    write_id_list(
+        out,
+        "Attachment_Calibers",
+        "Attachment_Caliber",
+        &item.attachment_calibers,
+    );
+
+    if !item.magazine_replacements.is_empty() {
+        write_value(
+            out,
+            "Magazine_Replacements",
+            &item.magazine_replacements.len(),
+        );
+        for (index, replacement) in item.magazine_replacements.iter().enumerate() {
+            write_value(
+                out,
+                &format!("Magazine_Replacement_{}_Map", index),
+                &replacement.map,
+            );
+            write_value(
+                out,
+                &format!("Magazine_Replacement_{}_ID", index),
+                &replacement.id,
+            );
         }
+    }
+}
 
-        Ok(ammo)
+impl ItemGunAsset {
+    /// Returns the magazine the gun spawns with on `map`.
+    ///
+    /// Like the game, map names are compared exactly, and the first matching replacement wins.
+    pub fn default_magazine_for_map(&self, map: &str) -> AssetReference<ItemMagazineAsset> {
+        self.magazine_replacements
+            .iter()
+            .find(|replacement| replacement.map == map)
+            .map_or(self.magazine_id, |replacement| replacement.id)
     }
 }
 
@@ -158,26 +416,64 @@ impl Parser<Vec<Hook>> for Hook {
     }
 }
 
-impl From<&str> for Action {
-    fn from(value: &str) -> Self {
-        match value {
-            "Trigger" => Self::Trigger,
-            "Bolt" => Self::Bolt,
-            "Pump" => Self::Pump,
-            "Rail" => Self::Rail,
-            "String" => Self::String,
-            "Break" => Self::Break,
-            "Rocket" => Self::Rocket,
-            "Minigun" => Self::Minigun,
-            _ => Self::None,
+impl MagazineReplacement {
+    /// Reads the legacy `Magazine_Replacement_#_...` keys, reporting values that do not parse.
+    pub fn read_replacements(
+        content: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<Vec<MagazineReplacement>> {
+        let mut count = 0;
+        let mut replacements: BTreeMap<usize, MagazineReplacement> = BTreeMap::new();
+
+        for line in content.lines() {
+            let mut split = line.split_whitespace();
+
+            let field = split.next().unwrap_or("");
+            let value = split.next().unwrap_or("");
+
+            if field == "Magazine_Replacements" {
+                if let Some(value) = dat::read_or_report(field, value, diagnostics)? {
+                    count = value;
+                }
+                continue;
+            }
+
+            let Some(rest) = field.strip_prefix("Magazine_Replacement_") else {
+                continue;
+            };
+            let Some((index, key)) = rest.split_once('_') else {
+                continue;
+            };
+            let Ok(index) = index.parse::<usize>() else {
+                continue;
+            };
+
+            let replacement = replacements.entry(index).or_default();
+            match key {
+                "Map" => replacement.map = value.into(),
+                "ID" => {
+                    if let Some(id) = dat::read_or_report(field, value, diagnostics)? {
+                        replacement.id = id;
+                    }
+                }
+                _ => {}
+            }
         }
+
+        // Only the first `Magazine_Replacements` entries are read by the game.
+        Ok(replacements
+            .into_iter()
+            .filter(|(index, _)| *index < count)
+            .map(|(_, replacement)| replacement)
+            .collect())
     }
 }
 
-impl Parser<Vec<Firemode>> for Firemode {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        _directory: &P,
+impl Firemode {
+    /// Reads every firemode flag, reporting a `Burst` amount that does not parse.
+    pub fn read_firemodes(
         content: &str,
+        diagnostics: &mut Vec<Diagnostic>,
     ) -> anyhow::Result<Vec<Firemode>> {
         let mut firemodes = Vec::new();
 
@@ -192,7 +488,10 @@ impl Parser<Vec<Firemode>> for Firemode {
                 "Semi" => firemodes.push(Firemode::Semi),
                 "Auto" => firemodes.push(Firemode::Auto),
                 "Burst" => {
-                    let amount: i32 = value.parse().unwrap_or(0);
+                    let amount = match value {
+                        "" => 0,
+                        value => dat::read_or_report(field, value, diagnostics)?.unwrap_or(0),
+                    };
 
                     firemodes.push(Firemode::Burst(amount))
                 }
@@ -0,0 +1,64 @@
+use std::path::Path;
+
+use crate::parser::dat::{DatAsset, Diagnostic};
+use crate::source::AssetSource;
+
+use super::spotlight::SpotLightConfig;
+use super::weapon::ItemWeaponAsset;
+
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+#[dat(finish = read_spotlight, write = write_spotlight)]
+pub struct ItemMeleeAsset {
+    #[serde(rename = "base")]
+    #[dat(flatten)]
+    pub item_weapon_asset: ItemWeaponAsset,
+
+    /// Multiplier on damage of the charged (“Secondary”) attack.
+    #[dat(key = "Strength")]
+    pub strength: f32,
+
+    /// Amount of stamina consumed by the charged attack.
+    #[dat(key = "Stamina")]
+    pub stamina: u8,
+
+    /// If the weapon can repair barricades, structures and vehicles.
+    #[dat(key = "Repair", flag)]
+    pub is_repair: bool,
+
+    /// If holding down the “Primary” action keeps attacking.
+    #[dat(key = "Repeated", flag)]
+    pub is_repeated: bool,
+
+    /// If it provides a toggleable light source.
+    #[dat(key = "Light", flag)]
+    pub is_light: bool,
+
+    /// Light source properties, present when `is_light` is set.
+    #[dat(skip, schema = SpotLightConfig)]
+    pub spotlight: Option<SpotLightConfig>,
+}
+
+fn read_spotlight(
+    item: &mut ItemMeleeAsset,
+    source: &dyn AssetSource,
+    directory: &Path,
+    content: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    if item.is_light {
+        item.spotlight = Some(SpotLightConfig::read_dat(
+            source,
+            directory,
+            content,
+            diagnostics,
+        )?);
+    }
+
+    Ok(())
+}
+
+fn write_spotlight(item: &ItemMeleeAsset, out: &mut String) {
+    if let Some(spotlight) = &item.spotlight {
+        spotlight.write_dat(out);
+    }
+}
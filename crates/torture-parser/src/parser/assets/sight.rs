@@ -1,20 +1,45 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
 use anyhow::Context;
 
+use crate::parser::color::Color;
+use crate::parser::dat::{self, write_value, DatAsset, DatValue, Diagnostic};
 use crate::parser::Parser;
+use crate::source::AssetSource;
 
 use super::caliber::ItemCaliberAsset;
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+#[dat(
+    finish = read_distance_markers,
+    write = write_distance_markers,
+    keys = [
+        "Distance_Markers",
+        "Distance_Marker_#_Distance",
+        "Distance_Marker_#_LineOffset",
+        "Distance_Marker_#_Line_Offset",
+        "Distance_Marker_#_LineWidth",
+        "Distance_Marker_#_Line_Width",
+        "Distance_Marker_#_Side",
+        "Distance_Marker_#_HasLabel",
+        "Distance_Marker_#_Has_Label",
+        "Distance_Marker_#_Color",
+    ]
+)]
 pub struct ItemSightAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub item_caliber_asset: ItemCaliberAsset,
 
     /// Multiplicative amount of zoom.
     ///
     /// This value must be equal to or greater than 1.
+    #[dat(key = "Zoom")]
     pub zoom: f32,
 
     /// This sight should be holographic.
+    #[dat(key = "Holographic", flag)]
     pub holographic: bool,
 
     /// Set a unique lighting vision effect to use.
@@ -22,10 +47,31 @@ pub struct ItemSightAsset {
     /// The value of this property may effect the default values of other properties.
     ///
     /// The Headlamp enumerator is not supported by this property.
+    #[dat(key = "Vision")]
     pub vision: Vision,
-    // List of distance markers, they are to add visible (and accurate) distance markers to the scope that account for the weapon’s bullet drop.
-    // pub distance_markers: Vec<DistanceMarker>,
-    // TODO: Nightvision stuffs
+
+    /// Color of the nightvision lighting.
+    ///
+    /// Defaults depend on `vision`, see [`Vision`].
+    #[dat(
+        key = "Nightvision_Color",
+        legacy_rgb,
+        default = item.vision.nightvision_defaults().0
+    )]
+    pub nightvision_color: Color,
+
+    /// Intensity of the nightvision fog.
+    ///
+    /// Defaults depend on `vision`, see [`Vision`].
+    #[dat(
+        key = "Nightvision_Fog_Intensity",
+        default = item.vision.nightvision_defaults().1
+    )]
+    pub nightvision_fog_intensity: f32,
+
+    /// List of distance markers, they are to add visible (and accurate) distance markers to the scope that account for the weapon’s bullet drop.
+    #[dat(skip)]
+    pub distance_markers: Vec<DistanceMarker>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -49,21 +95,23 @@ pub struct DistanceMarker {
     pub side: Side,
 
     /// If true, a label with Distance text is shown next to the horizontal line marker.
-    pub has_label: f32,
-    // TODO: Add Color32
-    //pub color: Color32
+    pub has_label: bool,
+
+    /// Color of the line and label.
+    pub color: Color,
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatValue, serde::Serialize, serde::Deserialize)]
 pub enum Side {
     /// Marking extends to the left from the center.
     Left,
 
     /// Marking extends to the right from the center.
+    #[default]
     Right,
 }
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatValue, serde::Serialize, serde::Deserialize)]
 pub enum Vision {
     /// There is no vision effect, and normal lighting is used.
     #[default]
@@ -76,52 +124,217 @@ pub enum Vision {
     Headlamp,
 }
 
-impl From<&str> for Side {
-    fn from(value: &str) -> Self {
-        match value {
-            "Left" => Self::Left,
-            "Right" => Self::Right,
-            _ => Self::Left,
+impl Vision {
+    /// Nightvision color and fog intensity used when the asset does not specify them.
+    pub fn nightvision_defaults(&self) -> (Color, f32) {
+        match self {
+            Self::Military => (Color::rgb(0x50, 0x78, 0x14), 0.25),
+            Self::Civilian => (Color::rgb(0x66, 0x66, 0x66), 0.5),
+            Self::None | Self::Headlamp => (Color::BLACK, 0.0),
         }
     }
 }
 
-impl From<&str> for Vision {
-    fn from(value: &str) -> Self {
-        match value {
-            "Military" => Self::Military,
-            "Civilian" => Self::Civilian,
-            "Headlamp" => Self::Headlamp,
-            _ => Self::None,
+fn read_distance_markers(
+    item: &mut ItemSightAsset,
+    _source: &dyn AssetSource,
+    _directory: &Path,
+    content: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    item.distance_markers = DistanceMarker::read_markers(content, diagnostics)?;
+
+    Ok(())
+}
+
+fn write_distance_markers(item: &ItemSightAsset, out: &mut String) {
+    DistanceMarker::write_list(&item.distance_markers, out);
+}
+
+impl Default for DistanceMarker {
+    fn default() -> Self {
+        Self {
+            distance: 0.0,
+            line_offset: 0.0,
+            line_width: 0.05,
+            side: Side::default(),
+            has_label: true,
+            color: Color::WHITE,
         }
     }
 }
 
-impl Parser<ItemSightAsset> for ItemSightAsset {
-    fn parse<P: AsRef<std::path::Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemSightAsset> {
-        let item_caliber_asset = ItemCaliberAsset::parse(directory, content)?;
-        let mut item = ItemSightAsset {
-            item_caliber_asset,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
+impl DistanceMarker {
+    /// Sets a single marker property, accepting both `LineOffset` and `Line_Offset` style keys.
+    fn set(
+        &mut self,
+        key: &str,
+        value: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<()> {
+        match key {
+            "Distance" => {
+                self.distance = value
+                    .parse()
+                    .context("Failed to parse marker Distance as f32")?
+            }
+            "LineOffset" | "Line_Offset" => {
+                self.line_offset = value
+                    .parse()
+                    .context("Failed to parse marker LineOffset as f32")?
+            }
+            "LineWidth" | "Line_Width" => {
+                self.line_width = value
+                    .parse()
+                    .context("Failed to parse marker LineWidth as f32")?
+            }
+            "Side" => {
+                if let Some(side) = dat::read_or_report(key, value, diagnostics)? {
+                    self.side = side;
+                }
+            }
+            "HasLabel" | "Has_Label" => self.has_label = value.parse().unwrap_or(true),
+            "Color" => {
+                if let Some(color) = dat::read_or_report(key, value, diagnostics)? {
+                    self.color = color;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Reads the list syntax, starting right after the `Distance_Markers` key:
+    ///
+    /// ```text
+    /// Distance_Markers
+    /// [
+    ///     {
+    ///         Distance 100
+    ///         Side Left
+    ///     }
+    /// ]
+    /// ```
+    fn parse_list<'a>(
+        lines: &mut impl Iterator<Item = &'a str>,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<Vec<Self>> {
+        let mut markers = Vec::new();
+        let mut current: Option<DistanceMarker> = None;
+
+        for line in lines {
             let mut split = line.split_whitespace();
 
             let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
+            let value = split.remainder().unwrap_or("").trim();
 
             match field {
-                "Vision" => item.vision = value.into(),
-                "Zoom" => item.zoom = value.parse().context("Failed to parse Zoom as f32")?,
-                "Holographic" => item.holographic = true,
-                _ => {}
+                "" | "[" => {}
+                "]" => break,
+                "{" => current = Some(DistanceMarker::default()),
+                "}" => markers.extend(current.take()),
+                _ => {
+                    if let Some(marker) = current.as_mut() {
+                        marker.set(field, value, diagnostics)?;
+                    }
+                }
             }
         }
 
-        Ok(item)
+        Ok(markers)
+    }
+
+    /// Writes markers in the list syntax read by [`DistanceMarker::parse_list`].
+    fn write_list(markers: &[DistanceMarker], out: &mut String) {
+        if markers.is_empty() {
+            return;
+        }
+
+        out.push_str("Distance_Markers\n[\n");
+        for marker in markers {
+            let mut fields = String::new();
+            write_value(&mut fields, "Distance", &marker.distance);
+            write_value(&mut fields, "LineOffset", &marker.line_offset);
+            write_value(&mut fields, "LineWidth", &marker.line_width);
+            write_value(&mut fields, "Side", &marker.side);
+            write_value(&mut fields, "HasLabel", &marker.has_label);
+            write_value(&mut fields, "Color", &marker.color);
+
+            out.push_str("\t{\n");
+            for line in fields.lines() {
+                out.push_str("\t\t");
+                out.push_str(line);
+                out.push('\n');
+            }
+            out.push_str("\t}\n");
+        }
+        out.push_str("]\n");
+    }
+}
+
+impl DistanceMarker {
+    /// Reads markers in either the list syntax or the legacy indexed syntax:
+    ///
+    /// ```text
+    /// Distance_Markers 1
+    /// Distance_Marker_0_Distance 100
+    /// Distance_Marker_0_Side Left
+    /// ```
+    pub fn read_markers(
+        content: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<Vec<DistanceMarker>> {
+        let mut count = 0;
+        let mut legacy: BTreeMap<usize, DistanceMarker> = BTreeMap::new();
+        let mut lines = content.lines();
+
+        while let Some(line) = lines.next() {
+            let mut split = line.split_whitespace();
+
+            let field = split.next().unwrap_or("");
+            let value = split.remainder().unwrap_or("").trim();
+
+            if field == "Distance_Markers" {
+                if value.is_empty() || value == "[" {
+                    return DistanceMarker::parse_list(&mut lines, diagnostics);
+                }
+
+                count = value
+                    .parse()
+                    .context("Failed to parse Distance_Markers as usize")?;
+                continue;
+            }
+
+            let Some(rest) = field.strip_prefix("Distance_Marker_") else {
+                continue;
+            };
+            let Some((index, key)) = rest.split_once('_') else {
+                continue;
+            };
+            let Ok(index) = index.parse::<usize>() else {
+                continue;
+            };
+
+            legacy
+                .entry(index)
+                .or_default()
+                .set(key, value, diagnostics)?;
+        }
+
+        Ok(legacy
+            .into_iter()
+            .filter(|(index, _)| *index < count)
+            .map(|(_, marker)| marker)
+            .collect())
+    }
+}
+
+impl Parser<Vec<DistanceMarker>> for DistanceMarker {
+    fn parse<P: AsRef<Path> + ?Sized>(
+        _directory: &P,
+        content: &str,
+    ) -> anyhow::Result<Vec<DistanceMarker>> {
+        DistanceMarker::read_markers(content, &mut Vec::new())
     }
 }
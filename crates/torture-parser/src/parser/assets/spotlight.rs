@@ -0,0 +1,24 @@
+use crate::parser::color::Color;
+use crate::parser::dat::DatAsset;
+
+/// Light source of flashlights, headlamps and lit melee weapons.
+///
+/// See [PlayerSpotLightConfig](https://docs.smartlydressedgames.com/en/stable/data/struct/playerspotlightconfig.html#doc-data-playerspotlightconfig).
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct SpotLightConfig {
+    /// Maximum distance the light reaches, in meters.
+    #[dat(key = "SpotLight_Range", default = 64.0)]
+    pub range: f32,
+
+    /// Angle of the light cone, in degrees.
+    #[dat(key = "SpotLight_Angle", default = 90.0)]
+    pub angle: f32,
+
+    /// Brightness of the light.
+    #[dat(key = "SpotLight_Intensity", default = 1.3)]
+    pub intensity: f32,
+
+    /// Color of the light.
+    #[dat(key = "SpotLight_Color", legacy_rgb, default = Color::rgb(245, 223, 147))]
+    pub color: Color,
+}
@@ -1,52 +1,22 @@
-use anyhow::Context;
+use crate::parser::dat::DatAsset;
 
 use super::barricade::ItemBarricadeAsset;
-use super::Parser;
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct ItemStorageAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub item_barricade_asset: ItemBarricadeAsset,
 
     /// Number of rows (vertical storage space).
+    #[dat(key = "Storage_Y")]
     pub height: u8,
 
     /// Number of columns (horizontal storage space).
+    #[dat(key = "Storage_X")]
     pub width: u8,
 
     /// If specified, the first item in the storage will be visibly displayed.
+    #[dat(key = "Display", flag)]
     pub display: bool,
 }
-
-impl Parser<ItemStorageAsset> for ItemStorageAsset {
-    fn parse<P: AsRef<std::path::Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemStorageAsset> {
-        let item_barricade_asset = ItemBarricadeAsset::parse(directory, content)?;
-        let mut item = ItemStorageAsset {
-            item_barricade_asset,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Storage_Y" => {
-                    item.height = value.parse().context("Failed to parse Storage_Y to a u8")?
-                }
-                "Storage_X" => {
-                    item.width = value.parse().context("Failed to parse Storage_X to a u8")?
-                }
-                "Display" => item.display = true,
-                _ => {}
-            }
-        }
-
-        Ok(item)
-    }
-}
@@ -1,55 +1,69 @@
 use std::path::Path;
 
+use crate::parser::color::Color;
+use crate::parser::dat::{DatAsset, Diagnostic};
+use crate::source::AssetSource;
+
 use super::caliber::ItemCaliberAsset;
-use super::Parser;
+use super::spotlight::SpotLightConfig;
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+#[dat(finish = read_spotlight, write = write_spotlight)]
 pub struct ItemTacticalAsset {
     #[serde(rename = "base")]
+    #[dat(flatten)]
     pub item_caliber_asset: ItemCaliberAsset,
 
     /// If it provides a toggleable laser.
+    #[dat(key = "Laser", flag)]
     pub laser: bool,
 
     /// If it provides a toggleable flashlight, and allows for using [PlayerSpotLightConfig](https://docs.smartlydressedgames.com/en/stable/data/struct/playerspotlightconfig.html#doc-data-playerspotlightconfig) properties.
+    #[dat(key = "Light", flag)]
     pub light: bool,
 
-    // TODO: Add light config
+    /// Light source properties, present when `light` is set.
+    #[dat(skip, schema = SpotLightConfig)]
+    pub spotlight: Option<SpotLightConfig>,
+
     /// If it provides a toggleable rangefinder.
+    #[dat(key = "Rangefinder", flag)]
     pub rangefinder: bool,
 
     /// If it provides the ability to perform a melee attack.
     ///
     /// This attack does 40 damage, and is not configurable.
+    #[dat(key = "Melee", flag)]
     pub melee: bool,
-    //pub laser_color: Color
+
+    /// Color of the laser.
+    ///
+    /// Defaults to red.
+    #[dat(key = "Laser_Color", legacy_rgb, default = Color::rgb(255, 0, 0))]
+    pub laser_color: Color,
+}
+
+fn read_spotlight(
+    item: &mut ItemTacticalAsset,
+    source: &dyn AssetSource,
+    directory: &Path,
+    content: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    if item.light {
+        item.spotlight = Some(SpotLightConfig::read_dat(
+            source,
+            directory,
+            content,
+            diagnostics,
+        )?);
+    }
+
+    Ok(())
 }
 
-impl Parser<ItemTacticalAsset> for ItemTacticalAsset {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemTacticalAsset> {
-        let item_caliber_asset = ItemCaliberAsset::parse(directory, content)?;
-        let mut item = ItemTacticalAsset {
-            item_caliber_asset,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-
-            match field {
-                "Laser" => item.laser = true,
-                "Light" => item.light = true,
-                "Rangefinder" => item.rangefinder = true,
-                "Melee" => item.melee = true,
-                _ => {}
-            }
-        }
-
-        Ok(item)
+fn write_spotlight(item: &ItemTacticalAsset, out: &mut String) {
+    if let Some(spotlight) = &item.spotlight {
+        spotlight.write_dat(out);
     }
 }
@@ -0,0 +1,36 @@
+use crate::parser::dat::DatAsset;
+
+use super::weapon::ItemWeaponAsset;
+
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+pub struct ItemThrowableAsset {
+    #[serde(rename = "base")]
+    #[dat(flatten)]
+    pub item_weapon_asset: ItemWeaponAsset,
+
+    /// If the throwable causes an area-of-effect explosion, using the damage properties of the weapon.
+    #[dat(key = "Explosive", flag)]
+    pub is_explosive: bool,
+
+    /// If the throwable blinds nearby players.
+    #[dat(key = "Flash", flag)]
+    pub is_flash: bool,
+
+    /// If the throwable sticks to whatever it hits.
+    #[dat(key = "Sticky", flag)]
+    pub is_sticky: bool,
+
+    /// If the throwable explodes when it hits something, instead of after `fuse_length`.
+    #[dat(key = "Explode_On_Impact", flag)]
+    pub explode_on_impact: bool,
+
+    /// Seconds between throwing and exploding.
+    ///
+    /// Defaults to 2.5.
+    #[dat(key = "Fuse_Length", default = 2.5)]
+    pub fuse_length: f32,
+
+    /// ID of the effect played when exploding.
+    #[dat(key = "Explosion")]
+    pub explosion: u16,
+}
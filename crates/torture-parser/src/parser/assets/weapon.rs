@@ -1,281 +1,110 @@
-use std::path::Path;
+use crate::parser::dat::DatAsset;
 
-use anyhow::Context;
+use super::BaseAsset;
 
-use super::{BaseAsset, Parser};
-
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct ItemWeaponAsset {
-    #[serde(flatten)]
+    #[serde(rename = "base")]
+    #[dat(flatten)]
     pub base_asset: BaseAsset,
 
+    #[dat(key = "Range")]
     pub range: f32,
 
+    #[dat(flatten)]
     pub player_damage: PlayerDamage,
+
+    #[dat(flatten)]
     pub zombie_damage: ZombieDamage,
+
+    #[dat(flatten)]
     pub animal_damage: AnimalDamage,
 
+    #[dat(key = "Barricade_Damage")]
     pub barricade_damage: f32,
+
+    #[dat(key = "Structure_Damage")]
     pub structure_damage: f32,
+
+    #[dat(key = "Vehicle_Damage")]
     pub vehicle_damage: f32,
+
+    #[dat(key = "Resource_Damage")]
     pub resource_damage: f32,
+
+    /// Defaults to the value of `resource_damage`.
+    #[dat(key = "Object_Damage", default = item.resource_damage)]
     pub object_damage: f32,
 
+    #[dat(key = "Durability")]
     pub durability: f32,
+
+    #[dat(key = "Wear")]
     pub wear: u8,
+
+    #[dat(key = "Invulnerable", flag)]
     pub invulnerable: bool,
 }
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct PlayerDamage {
+    #[dat(key = "Player_Damage")]
     pub amount: f32,
+
+    #[dat(key = "Player_Leg_Multiplier", default = 1.0)]
     pub leg_multiplier: f32,
+
+    #[dat(key = "Player_Arm_Multiplier", default = 1.0)]
     pub arm_multiplier: f32,
+
+    #[dat(key = "Player_Spine_Multiplier", default = 1.0)]
     pub spine_multiplier: f32,
+
+    #[dat(key = "Player_Skull_Multiplier", default = 1.0)]
     pub skull_multiplier: f32,
 
+    #[dat(key = "Player_Damage_Food")]
     pub food: f32,
+
+    #[dat(key = "Player_Damage_Water")]
     pub water: f32,
+
+    #[dat(key = "Player_Damage_Virus")]
     pub virus: f32,
+
+    #[dat(key = "Player_Damage_Hallucination")]
     pub hallucination: f32,
 }
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct ZombieDamage {
+    #[dat(key = "Zombie_Damage")]
     pub amount: f32,
+
+    #[dat(key = "Zombie_Leg_Multiplier", default = 1.0)]
     pub leg_multiplier: f32,
+
+    #[dat(key = "Zombie_Arm_Multiplier", default = 1.0)]
     pub arm_multiplier: f32,
+
+    #[dat(key = "Zombie_Spine_Multiplier", default = 1.0)]
     pub spine_multiplier: f32,
+
+    #[dat(key = "Zombie_Skull_Multiplier", default = 1.0)]
     pub skull_multiplier: f32,
 }
 
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
 pub struct AnimalDamage {
+    #[dat(key = "Animal_Damage")]
     pub amount: f32,
-    pub leg_multiplier: f32,
-    pub spine_multiplier: f32,
-    pub skull_multiplier: f32,
-}
 
-impl Parser<ItemWeaponAsset> for ItemWeaponAsset {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ItemWeaponAsset> {
-        let base_asset = BaseAsset::parse(directory, content)?;
-
-        let player_damage = PlayerDamage::parse(directory, content)?;
-        let zombie_damage = ZombieDamage::parse(directory, content)?;
-        let animal_damage = AnimalDamage::parse(directory, content)?;
-
-        let mut item = ItemWeaponAsset {
-            base_asset,
-            player_damage,
-            zombie_damage,
-            animal_damage,
-            ..Default::default()
-        };
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Range" => item.range = value.parse().context("Failed to parse Range as a f32")?,
-                "Durability" => {
-                    item.durability = value
-                        .parse()
-                        .context("Failed to parse Durability as a f32")?
-                }
-                "Wear" => item.wear = value.parse().context("Failed to parse Wear as a u8")?,
-                "Invulnerable" => item.invulnerable = true,
-                "Barricade_Damage" => {
-                    item.barricade_damage = value
-                        .parse()
-                        .context("Failed to parse Barricade_Damage as f32")?
-                }
-                "Structure_Damage" => {
-                    item.structure_damage = value
-                        .parse()
-                        .context("Failed to parse Structure_Damage as f32")?
-                }
-                "Vehicle_Damage" => {
-                    item.vehicle_damage = value
-                        .parse()
-                        .context("Failed to parse Vehicle_Damage as f32")?
-                }
-                "Resource_Damage" => {
-                    item.resource_damage = value
-                        .parse()
-                        .context("Failed to parse Resource_Damage as f32")?
-                }
-                "Object_Damage" => {
-                    item.object_damage = value
-                        .parse()
-                        .context("Failed to parse Object_Damage as f32")?
-                }
-                _ => {}
-            }
-        }
-
-        Ok(item)
-    }
-}
-
-impl Parser<PlayerDamage> for PlayerDamage {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        _directory: &P,
-        content: &str,
-    ) -> anyhow::Result<PlayerDamage> {
-        let mut damage = PlayerDamage::default();
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Player_Damage" => {
-                    damage.amount = value
-                        .parse()
-                        .context("Failed to parse Player_Damage as a f32")?
-                }
-                "Player_Damage_Food" => {
-                    damage.food = value
-                        .parse()
-                        .context("Failed to parse Player_Damage_Food as a f32")?
-                }
-                "Player_Damage_Water" => {
-                    damage.water = value
-                        .parse()
-                        .context("Failed to parse Player_Damage_Water as a f32")?
-                }
-                "Player_Damage_Virus" => {
-                    damage.virus = value
-                        .parse()
-                        .context("Failed to parse Player_Damage_Virus as a f32")?
-                }
-                "Player_Damage_Hallucination" => {
-                    damage.hallucination = value
-                        .parse()
-                        .context("Failed to parse Player_Damage_Hallucination as a f32")?
-                }
-                "Player_Leg_Multiplier" => {
-                    damage.leg_multiplier = value
-                        .parse()
-                        .context("Failed to parse Player_Leg_Multiplier as f32")?
-                }
-                "Player_Arm_Multiplier" => {
-                    damage.arm_multiplier = value
-                        .parse()
-                        .context("Failed to parse Player_Arm_Multiplier as f32")?
-                }
-                "Player_Spine_Multiplier" => {
-                    damage.spine_multiplier = value
-                        .parse()
-                        .context("Failed to parse Player_Spine_Multiplier as f32")?
-                }
-                "Player_Skull_Multiplier" => {
-                    damage.skull_multiplier = value
-                        .parse()
-                        .context("Failed to parse Player_Skull_Multiplier as f32")?
-                }
-                _ => {}
-            }
-        }
-
-        Ok(damage)
-    }
-}
+    #[dat(key = "Animal_Leg_Multiplier", default = 1.0)]
+    pub leg_multiplier: f32,
 
-impl Parser<ZombieDamage> for ZombieDamage {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        _directory: &P,
-        content: &str,
-    ) -> anyhow::Result<ZombieDamage> {
-        let mut damage = ZombieDamage::default();
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Zombie_Damage" => {
-                    damage.amount = value
-                        .parse()
-                        .context("Failed to parse Zombie_Damage as a f32")?
-                }
-                "Zombie_Leg_Multiplier" => {
-                    damage.leg_multiplier = value
-                        .parse()
-                        .context("Failed to parse Zombie_Leg_Multiplier as f32")?
-                }
-                "Zombie_Arm_Multiplier" => {
-                    damage.arm_multiplier = value
-                        .parse()
-                        .context("Failed to parse Zombie_Arm_Multiplier as f32")?
-                }
-                "Zombie_Spine_Multiplier" => {
-                    damage.spine_multiplier = value
-                        .parse()
-                        .context("Failed to parse Zombie_Spine_Multiplier as f32")?
-                }
-                "Zombie_Skull_Multiplier" => {
-                    damage.skull_multiplier = value
-                        .parse()
-                        .context("Failed to parse Zombie_Skull_Multiplier as f32")?
-                }
-                _ => {}
-            }
-        }
-
-        Ok(damage)
-    }
-}
+    #[dat(key = "Animal_Spine_Multiplier", default = 1.0)]
+    pub spine_multiplier: f32,
 
-impl Parser<AnimalDamage> for AnimalDamage {
-    fn parse<P: AsRef<Path> + ?Sized>(
-        _directory: &P,
-        content: &str,
-    ) -> anyhow::Result<AnimalDamage> {
-        let mut damage = AnimalDamage::default();
-
-        for line in content.lines() {
-            let mut split = line.split_whitespace();
-
-            let field = split.next().unwrap_or("");
-            let value = split.next().unwrap_or("");
-
-            match field {
-                "Animal_Damage" => {
-                    damage.amount = value
-                        .parse()
-                        .context("Failed to parse Animal_Damage as f32")?
-                }
-                "Animal_Leg_Multiplier" => {
-                    damage.leg_multiplier = value
-                        .parse()
-                        .context("Failed to parse Animal_Leg_Multiplier as f32")?
-                }
-                "Animal_Spine_Multiplier" => {
-                    damage.spine_multiplier = value
-                        .parse()
-                        .context("Failed to parse Animal_Spine_Multiplier as f32")?
-                }
-                "Animal_Skull_Multiplier" => {
-                    damage.skull_multiplier = value
-                        .parse()
-                        .context("Failed to parse Animal_Skull_Multiplier as f32")?
-                }
-                _ => {}
-            }
-        }
-
-        Ok(damage)
-    }
+    #[dat(key = "Animal_Skull_Multiplier", default = 1.0)]
+    pub skull_multiplier: f32,
 }
@@ -0,0 +1,197 @@
+//! Crafting recipes of an item, read from its legacy `Blueprint_#_...` keys:
+//!
+//! ```text
+//! Blueprints 1
+//! Blueprint_0_Type Supply
+//! Blueprint_0_Supplies 1
+//! Blueprint_0_Supply_0_ID 66
+//! Blueprint_0_Supply_0_Amount 2
+//! Blueprint_0_Products 1
+//! ```
+//!
+//! Blueprints written as a v2 list have a different layout, and are not read yet.
+
+use std::collections::BTreeMap;
+
+use super::assets::Asset;
+use super::dat::{self, write_value, Diagnostic, FromDatValue};
+use super::reference::AssetReference;
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Blueprint {
+    /// Tab of the crafting menu it is listed under, such as `Supply` or `Repair`.
+    pub r#type: String,
+
+    /// Items used up by crafting.
+    pub supplies: Vec<BlueprintItem>,
+
+    /// Item that is needed, but not used up.
+    pub tool: AssetReference<Asset>,
+
+    /// Items made, which is the item the blueprint belongs to unless it sets others.
+    pub outputs: Vec<BlueprintItem>,
+
+    /// Skill needed to craft, such as `Craft`, and its level.
+    pub skill: String,
+    pub level: u8,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BlueprintItem {
+    pub item: AssetReference<Asset>,
+    pub amount: u8,
+}
+
+/// A blueprint being read, whose lists are only cut to their counts once every key is seen.
+#[derive(Default)]
+struct Draft {
+    blueprint: Blueprint,
+    supplies: (usize, BTreeMap<usize, BlueprintItem>),
+    outputs: (usize, BTreeMap<usize, BlueprintItem>),
+    product: Option<AssetReference<Asset>>,
+    products: u8,
+}
+
+impl Blueprint {
+    /// Reads the blueprints of the item with the legacy ID `id`, reporting values the game
+    /// would not understand to `diagnostics`.
+    pub fn read_blueprints(
+        content: &str,
+        id: u16,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<Vec<Blueprint>> {
+        let mut count = 0;
+        let mut drafts: BTreeMap<usize, Draft> = BTreeMap::new();
+
+        for (field, value) in dat::lines(content) {
+            if field == "Blueprints" {
+                set(&mut count, field, value, diagnostics)?;
+                continue;
+            }
+
+            let Some(rest) = field.strip_prefix("Blueprint_") else {
+                continue;
+            };
+            let Some((index, key)) = rest.split_once('_') else {
+                continue;
+            };
+            let Ok(index) = index.parse::<usize>() else {
+                continue;
+            };
+
+            let draft = drafts.entry(index).or_default();
+            match key {
+                "Type" => draft.blueprint.r#type = value.to_string(),
+                "Supplies" => set(&mut draft.supplies.0, field, value, diagnostics)?,
+                "Outputs" => set(&mut draft.outputs.0, field, value, diagnostics)?,
+                "Product" => {
+                    if let Some(product) = dat::read_or_report(field, value, diagnostics)? {
+                        draft.product = Some(product);
+                    }
+                }
+                "Products" => set(&mut draft.products, field, value, diagnostics)?,
+                "Tool" => set(&mut draft.blueprint.tool, field, value, diagnostics)?,
+                "Skill" => draft.blueprint.skill = value.to_string(),
+                "Level" => set(&mut draft.blueprint.level, field, value, diagnostics)?,
+                key => {
+                    let (items, key) = if let Some(key) = key.strip_prefix("Supply_") {
+                        (&mut draft.supplies.1, key)
+                    } else if let Some(key) = key.strip_prefix("Output_") {
+                        (&mut draft.outputs.1, key)
+                    } else {
+                        continue;
+                    };
+                    let Some((index, key)) = key.split_once('_') else {
+                        continue;
+                    };
+                    let Ok(index) = index.parse::<usize>() else {
+                        continue;
+                    };
+
+                    let item = items.entry(index).or_insert(BlueprintItem {
+                        item: AssetReference::NONE,
+                        amount: 1,
+                    });
+                    match key {
+                        "ID" => set(&mut item.item, field, value, diagnostics)?,
+                        "Amount" => set(&mut item.amount, field, value, diagnostics)?,
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        // Only the first `Blueprints` blueprints, and the first `Supplies` and `Outputs` items of
+        // each, are read by the game.
+        Ok(drafts
+            .into_iter()
+            .filter(|(index, _)| *index < count)
+            .map(|(_, draft)| {
+                let mut blueprint = draft.blueprint;
+                blueprint.supplies = first(draft.supplies);
+                blueprint.outputs = first(draft.outputs);
+
+                if blueprint.outputs.is_empty() {
+                    blueprint.outputs.push(BlueprintItem {
+                        item: draft.product.unwrap_or(AssetReference::from_id(id)),
+                        amount: draft.products.max(1),
+                    });
+                }
+
+                blueprint
+            })
+            .collect())
+    }
+
+    /// Writes blueprints in the indexed syntax read by [`Blueprint::read_blueprints`], with
+    /// every output written out.
+    pub fn write_list(blueprints: &[Blueprint], out: &mut String) {
+        if blueprints.is_empty() {
+            return;
+        }
+
+        write_value(out, "Blueprints", &blueprints.len());
+        for (index, blueprint) in blueprints.iter().enumerate() {
+            let key = |key: &str| format!("Blueprint_{}_{}", index, key);
+
+            write_value(out, &key("Type"), &blueprint.r#type);
+            write_items(out, &key("Supplies"), &key("Supply"), &blueprint.supplies);
+            write_value(out, &key("Tool"), &blueprint.tool);
+            write_items(out, &key("Outputs"), &key("Output"), &blueprint.outputs);
+            write_value(out, &key("Skill"), &blueprint.skill);
+            if blueprint.level > 0 {
+                write_value(out, &key("Level"), &blueprint.level);
+            }
+        }
+    }
+}
+
+/// Reads `value` into `target`, or keeps `target` if the game would not understand it.
+fn set<T: FromDatValue>(
+    target: &mut T,
+    field: &str,
+    value: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    if let Some(value) = dat::read_or_report(field, value, diagnostics)? {
+        *target = value;
+    }
+
+    Ok(())
+}
+
+fn write_items(out: &mut String, count_key: &str, prefix: &str, items: &[BlueprintItem]) {
+    write_value(out, count_key, &items.len());
+    for (index, item) in items.iter().enumerate() {
+        write_value(out, &format!("{}_{}_ID", prefix, index), &item.item);
+        write_value(out, &format!("{}_{}_Amount", prefix, index), &item.amount);
+    }
+}
+
+fn first((count, items): (usize, BTreeMap<usize, BlueprintItem>)) -> Vec<BlueprintItem> {
+    items
+        .into_iter()
+        .filter(|(index, _)| *index < count)
+        .map(|(_, item)| item)
+        .collect()
+}
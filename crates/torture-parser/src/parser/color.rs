@@ -0,0 +1,157 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+/// An 8-bit per channel color, like Unity's `Color32`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 255 }
+    }
+
+    /// Formats the color as `#RRGGBB`, ignoring alpha.
+    pub fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+
+    /// Reads the legacy `<key>_R`, `<key>_G` and `<key>_B` byte properties.
+    ///
+    /// Returns `None` if none of the three are present, missing channels are 0.
+    pub fn parse_legacy_rgb(key: &str, content: &str) -> anyhow::Result<Option<Color>> {
+        let mut color = None;
+
+        for line in content.lines() {
+            let mut split = line.split_whitespace();
+
+            let field = split.next().unwrap_or("");
+            let value = split.next().unwrap_or("");
+
+            let Some(channel) = field
+                .strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix('_'))
+            else {
+                continue;
+            };
+            if !matches!(channel, "R" | "G" | "B") {
+                continue;
+            }
+
+            let color = color.get_or_insert(Color::BLACK);
+            match channel {
+                "R" => {
+                    color.r = value
+                        .parse()
+                        .with_context(|| format!("Failed to parse {} as u8", field))?
+                }
+                "G" => {
+                    color.g = value
+                        .parse()
+                        .with_context(|| format!("Failed to parse {} as u8", field))?
+                }
+                "B" => {
+                    color.b = value
+                        .parse()
+                        .with_context(|| format!("Failed to parse {} as u8", field))?
+                }
+                _ => {}
+            }
+        }
+
+        Ok(color)
+    }
+
+    /// Unity's named colors, which Unturned accepts as well.
+    fn from_name(name: &str) -> Option<Color> {
+        let color = match name.to_ascii_lowercase().as_str() {
+            "white" => Color::WHITE,
+            "black" => Color::BLACK,
+            "red" => Color::rgb(255, 0, 0),
+            "green" => Color::rgb(0, 255, 0),
+            "blue" => Color::rgb(0, 0, 255),
+            "yellow" => Color::rgb(255, 235, 4),
+            "cyan" => Color::rgb(0, 255, 255),
+            "magenta" => Color::rgb(255, 0, 255),
+            "gray" | "grey" => Color::rgb(127, 127, 127),
+            "clear" => Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            },
+            _ => return None,
+        };
+
+        Some(color)
+    }
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::WHITE
+    }
+}
+
+impl FromStr for Color {
+    type Err = anyhow::Error;
+
+    /// Parses `#RRGGBB`, `RRGGBB`, `r,g,b` (either 0-255 or 0-1 decimals) and named colors.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+
+        if let Some(color) = Self::from_name(value) {
+            return Ok(color);
+        }
+
+        if value.contains(',') {
+            let channels: Vec<&str> = value.split(',').map(str::trim).collect();
+            if channels.len() != 3 && channels.len() != 4 {
+                return Err(anyhow!("Expected 3 or 4 color channels, got '{}'", value));
+            }
+
+            // Unity writes decimal colors from 0 to 1, hand-written ones tend to use bytes.
+            let is_decimal = channels.iter().any(|channel| channel.contains('.'));
+            let mut bytes = [255u8; 4];
+            for (byte, channel) in bytes.iter_mut().zip(&channels) {
+                *byte = if is_decimal {
+                    let channel: f32 = channel
+                        .parse()
+                        .with_context(|| format!("Failed to parse color channel '{}'", channel))?;
+                    (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+                } else {
+                    channel
+                        .parse()
+                        .with_context(|| format!("Failed to parse color channel '{}'", channel))?
+                };
+            }
+
+            return Ok(Color {
+                r: bytes[0],
+                g: bytes[1],
+                b: bytes[2],
+                a: bytes[3],
+            });
+        }
+
+        let hex = value.strip_prefix('#').unwrap_or(value);
+        if hex.len() != 6 || !hex.is_ascii() {
+            return Err(anyhow!("Invalid color, got '{}'", value));
+        }
+
+        let channel = |range: std::ops::Range<usize>| {
+            u8::from_str_radix(&hex[range], 16)
+                .with_context(|| format!("Invalid hex color, got '{}'", value))
+        };
+
+        Ok(Color::rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?))
+    }
+}
@@ -0,0 +1,322 @@
+//! Reading and writing `.dat` key-value pairs, used by `#[derive(DatAsset)]`.
+
+use std::fmt;
+use std::path::Path;
+
+use anyhow::Context;
+
+use super::color::Color;
+use super::document;
+use super::guid::Guid;
+use super::reference::AssetReference;
+use super::vector::Vector3;
+use crate::source::{AssetSource, FileSystem};
+
+pub use torture_parser_derive::{DatAsset, DatValue};
+
+/// An asset whose fields map to keys of a `.dat` file.
+///
+/// Implement this with `#[derive(DatAsset)]`, which also implements [`Parser`](super::Parser)
+/// and [`Defaults`](super::defaults::Defaults).
+pub trait DatAsset: Sized {
+    /// Parses the asset, adding values the game would not understand to `diagnostics`.
+    ///
+    /// `content` is legacy key-value lines, and files next to the asset such as `English.dat`
+    /// are read from `directory` in `source`. [`Parser::parse`](super::Parser::parse) calls
+    /// this through [`parse_with_diagnostics`](Self::parse_with_diagnostics), dropping the
+    /// diagnostics.
+    fn read_dat(
+        source: &dyn AssetSource,
+        directory: &Path,
+        content: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> anyhow::Result<Self>;
+
+    /// Parses the asset along with every diagnostic found while reading it, reading the files
+    /// next to it from `source`.
+    ///
+    /// Content in the v2 format is read from its first `Asset` block, see [`document`].
+    fn parse_from<P: AsRef<Path> + ?Sized>(
+        source: &dyn AssetSource,
+        directory: &P,
+        content: &str,
+    ) -> anyhow::Result<(Self, Vec<Diagnostic>)> {
+        let content = document::first_asset(content)?;
+        let mut diagnostics = Vec::new();
+        let item = Self::read_dat(source, directory.as_ref(), &content, &mut diagnostics)?;
+
+        Ok((item, diagnostics))
+    }
+
+    /// Parses the asset from the filesystem, see [`parse_from`](Self::parse_from).
+    fn parse_with_diagnostics<P: AsRef<Path> + ?Sized>(
+        directory: &P,
+        content: &str,
+    ) -> anyhow::Result<(Self, Vec<Diagnostic>)> {
+        Self::parse_from(&FileSystem, directory, content)
+    }
+
+    /// Every key the asset reads, including those of flattened assets.
+    fn schema() -> Vec<DatField>;
+
+    /// Writes the asset back as `.dat` key-value pairs.
+    fn write_dat(&self, out: &mut String);
+}
+
+/// A key read by an asset, see [`DatAsset::schema`].
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct DatField {
+    pub key: &'static str,
+    pub kind: DatFieldKind,
+
+    /// Rust type the value is read as, empty for [`Finish`](DatFieldKind::Finish) and
+    /// [`Ignored`](DatFieldKind::Ignored) keys.
+    pub value_type: &'static str,
+
+    /// Value used when the key is missing, if it is not the type's default.
+    pub default: Option<&'static str>,
+
+    /// Doc comment of the field.
+    pub description: &'static str,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize)]
+pub enum DatFieldKind {
+    /// `Key value`
+    Value,
+
+    /// `Key`, set when present.
+    Flag,
+
+    /// Read by the asset's `finish` function, so its value has no single type.
+    Finish,
+
+    /// Read by the game, but not by the parser.
+    Ignored,
+}
+
+/// A value that was ignored while reading an asset, because the game would not understand it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Diagnostic {
+    pub key: String,
+    pub value: String,
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} '{}': {}", self.key, self.value, self.message)
+    }
+}
+
+/// Error for a value the game does not understand, such as a misspelled enum variant, a GUID
+/// with too few digits, or a number that does not parse.
+///
+/// Parsers report these as a [`Diagnostic`] and keep the field's default, like the game does.
+/// Any other error fails the whole asset.
+#[derive(Debug)]
+pub struct UnknownValue(pub String);
+
+impl fmt::Display for UnknownValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for UnknownValue {}
+
+/// A value that can be read from the text after a key.
+pub trait FromDatValue: Sized {
+    fn from_dat_value(value: &str) -> anyhow::Result<Self>;
+}
+
+/// A value that can be written after a key.
+pub trait ToDatValue {
+    /// Text written after the key, the key is left out if this is empty.
+    fn to_dat_value(&self) -> String;
+}
+
+/// Splits every line of `content` into its key and the trimmed rest of the line.
+pub fn lines(content: &str) -> impl Iterator<Item = (&str, &str)> {
+    content.lines().map(|line| {
+        let mut split = line.split_whitespace();
+
+        let field = split.next().unwrap_or("");
+        let value = split.remainder().unwrap_or("").trim();
+
+        (field, value)
+    })
+}
+
+pub fn read_value<T: FromDatValue>(key: &str, value: &str) -> anyhow::Result<T> {
+    let name = std::any::type_name::<T>();
+    let name = name.rsplit("::").next().unwrap_or(name);
+
+    T::from_dat_value(value).with_context(|| format!("Failed to parse {} as {}", key, name))
+}
+
+/// Reads a value like [`read_value`], but reports an [`UnknownValue`] to `diagnostics` and
+/// returns `None` instead of failing.
+pub fn read_or_report<T: FromDatValue>(
+    key: &str,
+    value: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<Option<T>> {
+    match read_value(key, value) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => match error.downcast_ref::<UnknownValue>() {
+            Some(unknown) => {
+                diagnostics.push(Diagnostic {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    message: unknown.to_string(),
+                });
+
+                Ok(None)
+            }
+            None => Err(error),
+        },
+    }
+}
+
+pub fn write_value<T: ToDatValue>(out: &mut String, key: &str, value: &T) {
+    let value = value.to_dat_value();
+
+    if !value.is_empty() {
+        out.push_str(key);
+        out.push(' ');
+        out.push_str(&value);
+        out.push('\n');
+    }
+}
+
+pub fn write_flag(out: &mut String, key: &str) {
+    out.push_str(key);
+    out.push('\n');
+}
+
+/// Numbers only read the first word after the key, like the game.
+macro_rules! number_value {
+    ($($type:ty),*) => {
+        $(
+            impl FromDatValue for $type {
+                fn from_dat_value(value: &str) -> anyhow::Result<Self> {
+                    let value = value.split_whitespace().next().unwrap_or("");
+                    value.parse().map_err(|error| {
+                        UnknownValue(format!("'{}' is not a number: {}", value, error)).into()
+                    })
+                }
+            }
+
+            impl ToDatValue for $type {
+                fn to_dat_value(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+number_value!(u8, u16, u32, u64, usize, i8, i16, i32, i64, f32, f64);
+
+/// A missing value means `true`, so `Hair_Visible` alone is the same as `Hair_Visible true`.
+impl FromDatValue for bool {
+    fn from_dat_value(value: &str) -> anyhow::Result<Self> {
+        match value.split_whitespace().next() {
+            None => Ok(true),
+            Some(value) => value
+                .to_lowercase()
+                .parse()
+                .map_err(|_| UnknownValue(format!("'{}' is not true or false", value)).into()),
+        }
+    }
+}
+
+impl ToDatValue for bool {
+    fn to_dat_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FromDatValue for String {
+    fn from_dat_value(value: &str) -> anyhow::Result<Self> {
+        Ok(value.to_string())
+    }
+}
+
+impl ToDatValue for String {
+    fn to_dat_value(&self) -> String {
+        self.clone()
+    }
+}
+
+impl FromDatValue for Color {
+    fn from_dat_value(value: &str) -> anyhow::Result<Self> {
+        value
+            .parse()
+            .map_err(|error: anyhow::Error| UnknownValue(error.to_string()).into())
+    }
+}
+
+impl ToDatValue for Color {
+    fn to_dat_value(&self) -> String {
+        self.to_hex()
+    }
+}
+
+impl FromDatValue for Vector3 {
+    fn from_dat_value(value: &str) -> anyhow::Result<Self> {
+        value
+            .parse()
+            .map_err(|error: anyhow::Error| UnknownValue(error.to_string()).into())
+    }
+}
+
+impl ToDatValue for Vector3 {
+    fn to_dat_value(&self) -> String {
+        format!("({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl FromDatValue for Guid {
+    fn from_dat_value(value: &str) -> anyhow::Result<Self> {
+        value
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .parse()
+            .map_err(|error: anyhow::Error| UnknownValue(error.to_string()).into())
+    }
+}
+
+/// The nil GUID is left out, as it means no GUID was set.
+impl ToDatValue for Guid {
+    fn to_dat_value(&self) -> String {
+        if self.is_nil() {
+            String::new()
+        } else {
+            self.to_string()
+        }
+    }
+}
+
+impl<T> FromDatValue for AssetReference<T> {
+    fn from_dat_value(value: &str) -> anyhow::Result<Self> {
+        value
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .parse()
+            .map_err(|error: anyhow::Error| UnknownValue(error.to_string()).into())
+    }
+}
+
+impl<T> ToDatValue for AssetReference<T> {
+    fn to_dat_value(&self) -> String {
+        if self.is_none() {
+            String::new()
+        } else {
+            self.to_string()
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Values Unturned uses for keys that are missing from an asset or whose value cannot be read.
+//!
+//! Deriving `Default` leaves numbers at 0 and flags at false, which is often not what the
+//! game uses. Assets declare their real defaults with `#[dat(default = ...)]`, and
+//! `#[derive(DatAsset)]` applies them once parsing is done.
+
+use crate::parser::dat::{self, FromDatValue};
+
+/// Fills in every field whose key is missing from an asset, or whose value cannot be read, with
+/// the value the game uses.
+pub trait Defaults {
+    fn apply_defaults(&mut self, content: &str);
+}
+
+/// If `content` has a line for `key` whose value can be read as a `T`.
+pub fn has_value<T: FromDatValue>(content: &str, key: &str) -> bool {
+    dat::lines(content).any(|(field, value)| field == key && T::from_dat_value(value).is_ok())
+}
+
+/// If `content` has a line for `key`, regardless of its value.
+pub fn has_key(content: &str, key: &str) -> bool {
+    content
+        .lines()
+        .any(|line| line.split_whitespace().next() == Some(key))
+}
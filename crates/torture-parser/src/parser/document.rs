@@ -0,0 +1,336 @@
+//! The v2 `.dat` format, which wraps an asset in `Metadata` and `Asset` blocks:
+//!
+//! ```text
+//! Metadata
+//! {
+//!     GUID 7691f71f76e926e5d981d7e76ed779fc
+//!     Type "SDG.Unturned.ItemGunAsset, Assembly-CSharp"
+//! }
+//! Asset
+//! {
+//!     ID 363
+//!     Type Gun
+//! }
+//! ```
+//!
+//! Standalone `.asset` files may hold several pairs of blocks. [`asset_blocks`] turns each
+//! `Asset` block back into legacy key-value lines, so every parser reads both formats.
+
+use std::borrow::Cow;
+
+use anyhow::{anyhow, bail};
+
+use super::assets::Type;
+use super::dat::FromDatValue;
+
+/// A value of a v2 document.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(untagged)]
+pub enum Node {
+    /// A plain value, empty for keys written without one.
+    Value(String),
+    Dictionary(Vec<(String, Node)>),
+    List(Vec<Node>),
+}
+
+impl Node {
+    pub fn as_value(&self) -> Option<&str> {
+        match self {
+            Node::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_dictionary(&self) -> Option<&[(String, Node)]> {
+        match self {
+            Node::Dictionary(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// Value of the first `key` in a dictionary, keys are case-insensitive like in the game.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        self.as_dictionary()?
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, node)| node)
+    }
+}
+
+/// If `content` uses the v2 format, instead of legacy key-value lines.
+pub fn is_wrapped(content: &str) -> bool {
+    content.lines().any(|line| {
+        let key = line.trim().trim_end_matches('{').trim_end();
+        let key = key.trim_matches('"');
+
+        key == "Asset" || key == "Metadata"
+    })
+}
+
+/// Parses a whole v2 document, whose top level is a dictionary.
+pub fn parse(content: &str) -> anyhow::Result<Node> {
+    let mut reader = Reader {
+        chars: content.chars().collect(),
+        position: 0,
+        line: 1,
+    };
+
+    reader.dictionary(false)
+}
+
+/// Returns the content of every asset in a file, as legacy key-value lines.
+///
+/// Legacy content is returned as is. For v2 content, the GUID and `Type` of each `Metadata`
+/// block are added to the `Asset` block after it, unless it sets them itself. Nested
+/// dictionaries and lists have no legacy form and are left out.
+pub fn asset_blocks(content: &str) -> anyhow::Result<Vec<Cow<'_, str>>> {
+    if !is_wrapped(content) {
+        return Ok(vec![Cow::Borrowed(content)]);
+    }
+
+    let document = parse(content)?;
+    let Node::Dictionary(entries) = document else {
+        unreachable!("documents are dictionaries");
+    };
+
+    let mut blocks = Vec::new();
+    let mut metadata = None;
+
+    for (key, node) in &entries {
+        if key.eq_ignore_ascii_case("Metadata") {
+            metadata = Some(node);
+        } else if key.eq_ignore_ascii_case("Asset") {
+            blocks.push(Cow::Owned(legacy_lines(node, metadata.take())?));
+        }
+    }
+
+    Ok(blocks)
+}
+
+/// Returns the content of the first asset, see [`asset_blocks`].
+pub fn first_asset(content: &str) -> anyhow::Result<Cow<'_, str>> {
+    asset_blocks(content)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| anyhow!("Expected an Asset block"))
+}
+
+fn legacy_lines(asset: &Node, metadata: Option<&Node>) -> anyhow::Result<String> {
+    let entries = asset
+        .as_dictionary()
+        .ok_or_else(|| anyhow!("Expected Asset to be a dictionary"))?;
+    let mut out = String::new();
+
+    if let Some(metadata) = metadata {
+        let guid = metadata.get("GUID").and_then(Node::as_value);
+        if let (Some(guid), None) = (guid, asset.get("GUID")) {
+            out.push_str(&format!("GUID {}\n", guid));
+        }
+
+        let r#type = metadata.get("Type").and_then(Node::as_value);
+        if let (Some(r#type), None) = (r#type.and_then(class_type), asset.get("Type")) {
+            out.push_str(&format!("Type {}\n", r#type));
+        }
+    }
+
+    for (key, node) in entries {
+        let Node::Value(value) = node else {
+            continue;
+        };
+
+        if value.is_empty() {
+            out.push_str(&format!("{}\n", key));
+        } else {
+            out.push_str(&format!("{} {}\n", key, value.replace('\n', " ")));
+        }
+    }
+
+    Ok(out)
+}
+
+/// Maps a class name such as `SDG.Unturned.ItemGunAsset, Assembly-CSharp` to its [`Type`],
+/// for assets that only name their class in `Metadata`.
+fn class_type(class: &str) -> Option<String> {
+    let class = class.split(',').next()?.trim();
+    let class = class.rsplit('.').next()?;
+    let name = class.strip_suffix("Asset")?;
+    let name = name.strip_prefix("Item").unwrap_or(name);
+
+    Type::from_dat_value(name).ok().map(|_| name.to_string())
+}
+
+struct Reader {
+    chars: Vec<char>,
+    position: usize,
+    line: usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+
+        Some(c)
+    }
+
+    /// Skips whitespace, and `//` comments until the end of their line.
+    fn skip_blank(&mut self) {
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() {
+                self.bump();
+            } else if c == '/' && self.chars.get(self.position + 1) == Some(&'/') {
+                while !matches!(self.peek(), None | Some('\n')) {
+                    self.bump();
+                }
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Skips spaces and tabs, without leaving the line.
+    fn skip_inline(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace() && c != '\n') {
+            self.bump();
+        }
+    }
+
+    fn dictionary(&mut self, nested: bool) -> anyhow::Result<Node> {
+        let mut entries = Vec::new();
+
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None if nested => bail!("Expected '}}' before the end of the file"),
+                None => break,
+                Some('}') if nested => {
+                    self.bump();
+                    break;
+                }
+                Some('}') => bail!("Unexpected '}}' on line {}", self.line),
+                _ => {}
+            }
+
+            let key = self.key()?;
+            let value = self.value_after_key()?;
+            entries.push((key, value));
+        }
+
+        Ok(Node::Dictionary(entries))
+    }
+
+    fn list(&mut self) -> anyhow::Result<Node> {
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_blank();
+            match self.peek() {
+                None => bail!("Expected ']' before the end of the file"),
+                Some(']') => {
+                    self.bump();
+                    break;
+                }
+                _ => items.push(self.value()?),
+            }
+        }
+
+        Ok(Node::List(items))
+    }
+
+    fn key(&mut self) -> anyhow::Result<String> {
+        if self.peek() == Some('"') {
+            return self.quoted();
+        }
+
+        let mut key = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || matches!(c, '{' | '}' | '[' | ']' | '"') {
+                break;
+            }
+            key.push(c);
+            self.bump();
+        }
+
+        if key.is_empty() {
+            bail!("Expected a key on line {}", self.line);
+        }
+
+        Ok(key)
+    }
+
+    /// Reads the value of a key, which is on the same line unless it opens a block.
+    fn value_after_key(&mut self) -> anyhow::Result<Node> {
+        self.skip_inline();
+
+        match self.peek() {
+            None | Some('}') => Ok(Node::Value(String::new())),
+            Some('\n') => {
+                let start = (self.position, self.line);
+                self.skip_blank();
+
+                if matches!(self.peek(), Some('{' | '[')) {
+                    self.value()
+                } else {
+                    (self.position, self.line) = start;
+                    Ok(Node::Value(String::new()))
+                }
+            }
+            _ => self.value(),
+        }
+    }
+
+    fn value(&mut self) -> anyhow::Result<Node> {
+        match self.peek() {
+            Some('{') => {
+                self.bump();
+                self.dictionary(true)
+            }
+            Some('[') => {
+                self.bump();
+                self.list()
+            }
+            Some('"') => self.quoted().map(Node::Value),
+            _ => {
+                let mut value = String::new();
+                while let Some(c) = self.peek() {
+                    if c == '\n' || matches!(c, '}' | ']') {
+                        break;
+                    }
+                    value.push(c);
+                    self.bump();
+                }
+
+                Ok(Node::Value(value.trim().to_string()))
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> anyhow::Result<String> {
+        let line = self.line;
+        self.bump();
+
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => bail!("Unterminated quote starting on line {}", line),
+                Some('"') => break,
+                Some('\\') => match self.bump() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(c) => value.push(c),
+                    None => bail!("Unterminated quote starting on line {}", line),
+                },
+                Some(c) => value.push(c),
+            }
+        }
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+/// A 128-bit asset GUID, written as 32 hex digits like `7691f71f76e926e5d981d7e76ed779fc`.
+///
+/// The nil GUID (all zeroes) means no GUID was set.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Guid(pub u128);
+
+impl Guid {
+    pub const NIL: Guid = Guid(0);
+
+    pub fn is_nil(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl FromStr for Guid {
+    type Err = anyhow::Error;
+
+    /// Parses 32 hex digits, optionally grouped with dashes like `8-4-4-4-12`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let hex: String = value.trim().chars().filter(|c| *c != '-').collect();
+
+        if hex.len() != 32 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(anyhow!(
+                "Invalid GUID, expected 32 hex digits, got '{}'",
+                value
+            ));
+        }
+
+        Ok(Guid(u128::from_str_radix(&hex, 16)?))
+    }
+}
+
+impl fmt::Display for Guid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:032x}", self.0)
+    }
+}
+
+impl serde::Serialize for Guid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Guid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+
+        value.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use anyhow::Context;
+use crate::source::AssetSource;
 
 #[derive(Debug, Default, serde::Deserialize, serde::Serialize)]
 pub struct Language {
@@ -9,9 +9,8 @@ pub struct Language {
 }
 
 impl Language {
-    pub fn parse_language(path: &Path) -> anyhow::Result<Language> {
-        let content = std::fs::read_to_string(path)
-            .with_context(|| format!("Failed to read content for: {}", path.display()))?;
+    pub fn parse_language(source: &dyn AssetSource, path: &Path) -> anyhow::Result<Language> {
+        let content = source.read_to_string(path)?;
         let mut language = Language::default();
 
         for line in content.lines() {
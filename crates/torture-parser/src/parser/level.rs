@@ -0,0 +1,74 @@
+pub mod block;
+pub mod config;
+pub mod info;
+pub mod spawns;
+
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::source::{AssetSource, FileSystem};
+
+use config::LevelConfig;
+use info::LevelInfo;
+use spawns::SpawnTable;
+
+/// A map folder, such as `Maps/PEI` or a workshop map.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct Level {
+    /// Name of the map, which is the name of its folder.
+    ///
+    /// This is the value matched against by properties like `Magazine_Replacement_#_Map`.
+    pub name: String,
+
+    /// Contents of `Config.json`.
+    pub config: LevelConfig,
+
+    /// Contents of `Level.dat`.
+    pub info: LevelInfo,
+
+    /// Item spawn tables from `Spawns/Items.dat`.
+    pub item_tables: Vec<SpawnTable>,
+
+    /// Vehicle spawn tables from `Spawns/Vehicles.dat`.
+    pub vehicle_tables: Vec<SpawnTable>,
+
+    /// Animal spawn tables from `Spawns/Animals.dat`.
+    pub animal_tables: Vec<SpawnTable>,
+}
+
+impl Level {
+    /// Parses the map folder at `path`.
+    ///
+    /// `Config.json` and `Level.dat` are required, the spawn tables are optional since
+    /// some maps (e.g. arena maps) do not ship them.
+    pub fn parse_level(path: &Path) -> anyhow::Result<Level> {
+        Level::read_level(&FileSystem, path)
+    }
+
+    /// Parses the map folder at `path` in `source`, see [`parse_level`](Level::parse_level).
+    pub fn read_level(source: &dyn AssetSource, path: &Path) -> anyhow::Result<Level> {
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .with_context(|| format!("Failed to get map name for: {}", path.display()))?
+            .to_string();
+
+        let config = LevelConfig::parse_config(source, &path.join("Config.json"))?;
+        let info = LevelInfo::parse_info(source, &path.join("Level.dat"))?;
+
+        let spawns = path.join("Spawns");
+        let item_tables = SpawnTable::parse_items(source, &spawns.join("Items.dat"))?;
+        let vehicle_tables = SpawnTable::parse_vehicles(source, &spawns.join("Vehicles.dat"))?;
+        let animal_tables = SpawnTable::parse_animals(source, &spawns.join("Animals.dat"))?;
+
+        Ok(Level {
+            name,
+            config,
+            info,
+            item_tables,
+            vehicle_tables,
+            animal_tables,
+        })
+    }
+}
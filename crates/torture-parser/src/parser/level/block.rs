@@ -0,0 +1,57 @@
+use anyhow::{anyhow, Context};
+
+/// Sequential reader over the binary `.dat` files Unturned writes for levels.
+///
+/// Mirrors the game's `Block` type: integers and floats are little-endian,
+/// strings are prefixed with a single length byte, colors are three bytes.
+pub struct Block<'a> {
+    data: &'a [u8],
+    step: usize,
+}
+
+impl<'a> Block<'a> {
+    pub fn new(data: &'a [u8]) -> Block<'a> {
+        Block { data, step: 0 }
+    }
+
+    fn take(&mut self, count: usize) -> anyhow::Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.step..self.step + count)
+            .ok_or_else(|| anyhow!("Unexpected end of block at byte {}", self.step))?;
+        self.step += count;
+
+        Ok(bytes)
+    }
+
+    pub fn read_byte(&mut self) -> anyhow::Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_u16(&mut self) -> anyhow::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_u64(&mut self) -> anyhow::Result<u64> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into()?))
+    }
+
+    pub fn read_f32(&mut self) -> anyhow::Result<f32> {
+        let bytes = self.take(4)?;
+        Ok(f32::from_le_bytes(bytes.try_into()?))
+    }
+
+    pub fn read_string(&mut self) -> anyhow::Result<String> {
+        let length = self.read_byte()? as usize;
+        let bytes = self.take(length)?;
+
+        String::from_utf8(bytes.to_vec()).context("Failed to read string as UTF-8")
+    }
+
+    /// Reads an RGB color, as `[r, g, b]`.
+    pub fn read_color(&mut self) -> anyhow::Result<[u8; 3]> {
+        Ok([self.read_byte()?, self.read_byte()?, self.read_byte()?])
+    }
+}
@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::parser::assets::magazine::ItemMagazineAsset;
+use crate::parser::guid::Guid;
+use crate::parser::reference::AssetReference;
+use crate::source::AssetSource;
+
+/// Contents of a map's `Config.json`.
+///
+/// Keys are read in the game's `Pascal_Snake` casing, but serialized in snake case like
+/// every other struct in this crate.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LevelConfig {
+    /// Version of the map, such as `3.24.0.0`.
+    #[serde(alias = "Version")]
+    pub version: String,
+
+    #[serde(alias = "Creators")]
+    pub creators: Vec<String>,
+
+    #[serde(alias = "Collaborators")]
+    pub collaborators: Vec<String>,
+
+    #[serde(alias = "Thanks")]
+    pub thanks: Vec<String>,
+
+    /// ID of the map item given to players, or 0 if there is none.
+    #[serde(alias = "Item")]
+    pub item: u16,
+
+    /// Number of loading screen tips in the map's `English.dat`.
+    #[serde(alias = "Tips")]
+    pub tips: u32,
+
+    /// Either `Official`, `Curated` or `Misc`.
+    #[serde(alias = "Category")]
+    pub category: String,
+
+    /// Reference to the map's `LevelAsset`, which overrides gameplay assets such as
+    /// the crafting blacklist, skills and dropship.
+    #[serde(alias = "Asset")]
+    pub asset: Option<LevelAssetReference>,
+
+    /// Per-mode overrides of the server config, keyed by mode (e.g. `Normal`, `Hard`).
+    #[serde(alias = "Mode_Config_Overrides")]
+    pub mode_config_overrides: BTreeMap<String, serde_json::Value>,
+
+    /// Assets the map uses in place of others, such as a winter variant of a vanilla item.
+    #[serde(alias = "Asset_Overrides")]
+    pub asset_overrides: Vec<AssetOverride>,
+
+    /// Legacy item IDs the map uses in place of others, keyed by the replaced ID.
+    #[serde(alias = "Item_ID_Mappings")]
+    pub item_id_mappings: BTreeMap<u16, u16>,
+
+    /// Magazines the map uses in place of others for every gun, like a gun's own
+    /// `Magazine_Replacement_#_Map`.
+    #[serde(alias = "Magazine_Replacements")]
+    pub magazine_replacements: Vec<LevelMagazineReplacement>,
+
+    /// Every other key, kept as-is since maps regularly carry settings we do not model.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LevelAssetReference {
+    #[serde(alias = "GUID")]
+    pub guid: Guid,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AssetOverride {
+    #[serde(alias = "Original")]
+    pub original: Guid,
+
+    #[serde(alias = "Replacement")]
+    pub replacement: Guid,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LevelMagazineReplacement {
+    #[serde(alias = "Original")]
+    pub original: AssetReference<ItemMagazineAsset>,
+
+    #[serde(alias = "Replacement")]
+    pub replacement: AssetReference<ItemMagazineAsset>,
+}
+
+impl LevelConfig {
+    pub fn parse_config(source: &dyn AssetSource, path: &Path) -> anyhow::Result<LevelConfig> {
+        let content = source.read_to_string(path)?;
+
+        // Config.json is often saved by editors that prepend a byte order mark.
+        let content = content.trim_start_matches('\u{feff}');
+
+        serde_json::from_str(content)
+            .with_context(|| format!("Failed to parse Config.json: {}", path.display()))
+    }
+}
@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use anyhow::Context;
+
+use super::block::Block;
+use crate::source::AssetSource;
+
+/// Contents of a map's `Level.dat`.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct LevelInfo {
+    /// Save version of the file.
+    pub version: u8,
+
+    /// Size of the map.
+    pub size: LevelSize,
+
+    /// Gamemode the map was made for.
+    ///
+    /// Only stored since version 2, older files are always [`LevelType::Survival`].
+    pub r#type: LevelType,
+}
+
+#[derive(Debug, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub enum LevelSize {
+    Tiny,
+    Small,
+    #[default]
+    Medium,
+    Large,
+    Insane,
+}
+
+#[derive(Debug, Default, Copy, Clone, serde::Serialize, serde::Deserialize)]
+pub enum LevelType {
+    #[default]
+    Survival,
+    Horde,
+    Arena,
+}
+
+impl LevelInfo {
+    pub fn parse_info(source: &dyn AssetSource, path: &Path) -> anyhow::Result<LevelInfo> {
+        let content = source.read(path)?;
+        let mut block = Block::new(&content);
+
+        let version = block
+            .read_byte()
+            .context("Failed to read Level.dat version")?;
+        let size = block.read_byte().context("Failed to read Level.dat size")?;
+        let r#type = if version > 1 {
+            block.read_byte().context("Failed to read Level.dat type")?
+        } else {
+            0
+        };
+
+        Ok(LevelInfo {
+            version,
+            size: size.into(),
+            r#type: r#type.into(),
+        })
+    }
+}
+
+impl From<u8> for LevelSize {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => Self::Tiny,
+            1 => Self::Small,
+            2 => Self::Medium,
+            3 => Self::Large,
+            4 => Self::Insane,
+            _ => Self::Medium,
+        }
+    }
+}
+
+impl From<u8> for LevelType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Self::Horde,
+            2 => Self::Arena,
+            _ => Self::Survival,
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use super::block::Block;
+use crate::source::AssetSource;
+
+/// A level spawn table, as configured in the map editor.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct SpawnTable {
+    pub name: String,
+
+    /// Color of the table in the map editor, as `[r, g, b]`.
+    pub color: [u8; 3],
+
+    /// ID of the spawn asset this table resolves through, or 0 if the tiers below are used.
+    pub table_id: u16,
+
+    pub tiers: Vec<SpawnTier>,
+}
+
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct SpawnTier {
+    pub name: String,
+
+    /// Chance of this tier being picked, relative to the other tiers of the table.
+    pub chance: f32,
+
+    /// IDs of the items, vehicles or animals that can spawn from this tier.
+    pub ids: Vec<u16>,
+}
+
+impl SpawnTable {
+    /// Parses `Spawns/Items.dat`.
+    pub fn parse_items(source: &dyn AssetSource, path: &Path) -> anyhow::Result<Vec<SpawnTable>> {
+        Self::parse_tables(source, path, 3)
+    }
+
+    /// Parses `Spawns/Vehicles.dat`.
+    pub fn parse_vehicles(
+        source: &dyn AssetSource,
+        path: &Path,
+    ) -> anyhow::Result<Vec<SpawnTable>> {
+        Self::parse_tables(source, path, 3)
+    }
+
+    /// Parses `Spawns/Animals.dat`.
+    pub fn parse_animals(source: &dyn AssetSource, path: &Path) -> anyhow::Result<Vec<SpawnTable>> {
+        Self::parse_tables(source, path, 2)
+    }
+
+    /// Reads the tables at the start of a spawns file, ignoring the spawnpoints after them.
+    ///
+    /// `table_id_after` is the last file version that did not store a spawn asset ID per table.
+    /// A missing file means the map has no tables of that kind.
+    fn parse_tables(
+        source: &dyn AssetSource,
+        path: &Path,
+        table_id_after: u8,
+    ) -> anyhow::Result<Vec<SpawnTable>> {
+        if !source.is_file(path) {
+            return Ok(Vec::new());
+        }
+
+        let content = source.read(path)?;
+        let mut block = Block::new(&content);
+
+        let version = block.read_byte()?;
+        if version > 1 && version < 3 {
+            // Old files stored the Steam ID of the last editor.
+            block.read_u64()?;
+        }
+
+        let count = block.read_byte()?;
+        let mut tables = Vec::with_capacity(count as usize);
+
+        for _ in 0..count {
+            let color = block.read_color()?;
+            let name = block.read_string()?;
+            let table_id = if version > table_id_after {
+                block.read_u16()?
+            } else {
+                0
+            };
+
+            let tier_count = block.read_byte()?;
+            let mut tiers = Vec::with_capacity(tier_count as usize);
+
+            for _ in 0..tier_count {
+                let name = block.read_string()?;
+                let chance = block.read_f32()?;
+
+                let id_count = block.read_byte()?;
+                let mut ids = Vec::with_capacity(id_count as usize);
+
+                for _ in 0..id_count {
+                    ids.push(block.read_u16()?);
+                }
+
+                tiers.push(SpawnTier { name, chance, ids });
+            }
+
+            tables.push(SpawnTable {
+                name,
+                color,
+                table_id,
+                tiers,
+            });
+        }
+
+        Ok(tables)
+    }
+}
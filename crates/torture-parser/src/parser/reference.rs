@@ -0,0 +1,168 @@
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+use super::guid::Guid;
+
+/// A reference to another asset of type `T`, either by GUID or by its legacy `u16` ID.
+///
+/// Serialized as the ID number, or as the GUID string. No reference is serialized as `0`,
+/// which the game treats as no asset as well.
+pub struct AssetReference<T> {
+    target: Target,
+    asset: PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Target {
+    None,
+    Id(u16),
+    Guid(Guid),
+}
+
+impl<T> AssetReference<T> {
+    pub const NONE: Self = Self::new(Target::None);
+
+    const fn new(target: Target) -> Self {
+        Self {
+            target,
+            asset: PhantomData,
+        }
+    }
+
+    /// References an asset by its legacy ID, `0` is no reference.
+    pub const fn from_id(id: u16) -> Self {
+        if id == 0 {
+            Self::NONE
+        } else {
+            Self::new(Target::Id(id))
+        }
+    }
+
+    /// References an asset by its GUID, the nil GUID is no reference.
+    pub const fn from_guid(guid: Guid) -> Self {
+        if guid.0 == 0 {
+            Self::NONE
+        } else {
+            Self::new(Target::Guid(guid))
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.target == Target::None
+    }
+
+    /// Legacy ID of the asset, if it is referenced by ID.
+    pub fn id(&self) -> Option<u16> {
+        match self.target {
+            Target::Id(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// GUID of the asset, if it is referenced by GUID.
+    pub fn guid(&self) -> Option<Guid> {
+        match self.target {
+            Target::Guid(guid) => Some(guid),
+            _ => None,
+        }
+    }
+
+    /// If this references `id` or `guid`, which are both identifiers of the same asset.
+    pub fn is(&self, id: u16, guid: Guid) -> bool {
+        match self.target {
+            Target::None => false,
+            Target::Id(target) => target == id,
+            Target::Guid(target) => target == guid,
+        }
+    }
+}
+
+impl<T> Default for AssetReference<T> {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl<T> Clone for AssetReference<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AssetReference<T> {}
+
+impl<T> PartialEq for AssetReference<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.target == other.target
+    }
+}
+
+impl<T> Eq for AssetReference<T> {}
+
+impl<T> fmt::Debug for AssetReference<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.target {
+            Target::None => f.write_str("AssetReference::NONE"),
+            Target::Id(id) => write!(f, "AssetReference({})", id),
+            Target::Guid(guid) => write!(f, "AssetReference({})", guid),
+        }
+    }
+}
+
+impl<T> fmt::Display for AssetReference<T> {
+    /// Formats the reference the way it is written in a `.dat` file, no reference is `0`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.target {
+            Target::None => f.write_str("0"),
+            Target::Id(id) => write!(f, "{}", id),
+            Target::Guid(guid) => write!(f, "{}", guid),
+        }
+    }
+}
+
+impl<T> FromStr for AssetReference<T> {
+    type Err = anyhow::Error;
+
+    /// Parses a GUID if the value has 32 hex digits, and a legacy ID otherwise.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+
+        if let Ok(guid) = value.parse::<Guid>() {
+            return Ok(Self::from_guid(guid));
+        }
+
+        value
+            .parse::<u16>()
+            .map(Self::from_id)
+            .map_err(|_| anyhow!("Expected a legacy ID or a GUID, got '{}'", value))
+    }
+}
+
+impl<T> serde::Serialize for AssetReference<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.target {
+            Target::None => serializer.serialize_u16(0),
+            Target::Id(id) => serializer.serialize_u16(id),
+            Target::Guid(guid) => serde::Serialize::serialize(&guid, serializer),
+        }
+    }
+}
+
+impl<'de, T> serde::Deserialize<'de> for AssetReference<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Id(u16),
+            Guid(Guid),
+        }
+
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Id(id) => Self::from_id(id),
+            Repr::Guid(guid) => Self::from_guid(guid),
+        })
+    }
+}
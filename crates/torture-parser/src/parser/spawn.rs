@@ -0,0 +1,170 @@
+//! Spawn assets, which pick what to spawn from weighted tables of assets and other spawn
+//! assets.
+//!
+//! Tables are legacy `Table_#_...` keys, or a `Tables` list in the v2 format:
+//!
+//! ```text
+//! Tables 2
+//! Table_0_Asset_ID 363
+//! Table_0_Weight 10
+//! Table_1_Spawn_ID 229
+//! Table_1_Weight 5
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use super::assets::BaseAsset;
+use super::dat::{self, DatAsset, Diagnostic};
+use super::document::{self, Node};
+use super::guid::Guid;
+use crate::source::AssetSource;
+
+#[derive(Debug, Default, DatAsset, serde::Serialize, serde::Deserialize)]
+#[dat(
+    finish = read_tables,
+    keys = ["Tables", "Table_#_Asset_ID", "Table_#_Spawn_ID", "Table_#_Weight"],
+    ignore = ["Roots", "Root_#_Spawn_ID", "Root_#_Weight"]
+)]
+pub struct SpawnAsset {
+    /// Spawn assets have no `English.dat`, so this is named after its folder.
+    #[serde(rename = "base")]
+    #[dat(flatten)]
+    pub base_asset: BaseAsset,
+
+    /// Entries of the legacy `Table_#_...` keys, or of the v2 `Tables` list when parsed with
+    /// [`Asset::parse_all`](super::assets::Asset::parse_all).
+    #[dat(skip)]
+    pub tables: Vec<SpawnEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SpawnEntry {
+    pub target: SpawnTarget,
+
+    /// Chance of this entry being picked, relative to the other entries.
+    pub weight: i32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SpawnTarget {
+    /// Legacy ID of an item, vehicle or animal, depending on what is spawned.
+    Asset(u16),
+
+    /// Legacy ID of another spawn asset, which picks from its own tables.
+    Spawn(u16),
+
+    /// Any asset, including another spawn asset.
+    Guid(Guid),
+}
+
+fn read_tables(
+    item: &mut SpawnAsset,
+    _source: &dyn AssetSource,
+    directory: &Path,
+    content: &str,
+    _diagnostics: &mut Vec<Diagnostic>,
+) -> anyhow::Result<()> {
+    if item.base_asset.name.is_empty() {
+        if let Some(name) = directory.file_name() {
+            item.base_asset.name = name.to_string_lossy().to_string();
+        }
+    }
+    item.tables = legacy_tables(content)?;
+
+    Ok(())
+}
+
+fn legacy_tables(content: &str) -> anyhow::Result<Vec<SpawnEntry>> {
+    let mut count = 0;
+    let mut targets: BTreeMap<usize, SpawnTarget> = BTreeMap::new();
+    let mut weights: BTreeMap<usize, i32> = BTreeMap::new();
+
+    for (field, value) in dat::lines(content) {
+        if field == "Tables" {
+            count = dat::read_value(field, value)?;
+            continue;
+        }
+
+        let Some(rest) = field.strip_prefix("Table_") else {
+            continue;
+        };
+        let Some((index, key)) = rest.split_once('_') else {
+            continue;
+        };
+        let Ok(index) = index.parse::<usize>() else {
+            continue;
+        };
+
+        match key {
+            "Asset_ID" => {
+                targets.insert(index, SpawnTarget::Asset(dat::read_value(field, value)?));
+            }
+            "Spawn_ID" => {
+                targets.insert(index, SpawnTarget::Spawn(dat::read_value(field, value)?));
+            }
+            "Weight" => {
+                weights.insert(index, dat::read_value(field, value)?);
+            }
+            _ => {}
+        }
+    }
+
+    // Only the first `Tables` entries are read by the game.
+    Ok(targets
+        .into_iter()
+        .filter(|(index, _)| *index < count)
+        .map(|(index, target)| SpawnEntry {
+            target,
+            weight: weights.get(&index).copied().unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// The `Tables` list of every `Asset` block of a file in the v2 format, which is nested and so
+/// left out of [`document::asset_blocks`]. Blocks without one are `None`.
+pub(crate) fn nested_tables(content: &str) -> anyhow::Result<Vec<Option<Vec<SpawnEntry>>>> {
+    if !document::is_wrapped(content) {
+        return Ok(Vec::new());
+    }
+    let Node::Dictionary(root) = document::parse(content)? else {
+        return Ok(Vec::new());
+    };
+
+    root.iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case("Asset"))
+        .map(|(_, asset)| {
+            let Some(Node::List(tables)) = asset.get("Tables") else {
+                return Ok(None);
+            };
+
+            tables
+                .iter()
+                .map(nested_entry)
+                .filter_map(Result::transpose)
+                .collect::<anyhow::Result<Vec<_>>>()
+                .map(Some)
+        })
+        .collect()
+}
+
+/// Reads one entry of a v2 `Tables` list, or `None` if it spawns nothing.
+fn nested_entry(table: &Node) -> anyhow::Result<Option<SpawnEntry>> {
+    let value = |key: &str| table.get(key).and_then(Node::as_value);
+
+    let target = if let Some(id) = value("LegacyAssetId") {
+        SpawnTarget::Asset(dat::read_value("LegacyAssetId", id)?)
+    } else if let Some(id) = value("LegacySpawnId") {
+        SpawnTarget::Spawn(dat::read_value("LegacySpawnId", id)?)
+    } else if let Some(guid) = value("Guid") {
+        SpawnTarget::Guid(dat::read_value("Guid", guid)?)
+    } else {
+        return Ok(None);
+    };
+    let weight = value("Weight")
+        .map(|weight| dat::read_value("Weight", weight))
+        .transpose()?
+        .unwrap_or_default();
+
+    Ok(Some(SpawnEntry { target, weight }))
+}
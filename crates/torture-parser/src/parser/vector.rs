@@ -0,0 +1,46 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+
+/// A position or direction, like Unity's `Vector3`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Vector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Vector3 {
+    pub const ZERO: Vector3 = Vector3::new(0.0, 0.0, 0.0);
+    pub const ONE: Vector3 = Vector3::new(1.0, 1.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, z: f32) -> Vector3 {
+        Vector3 { x, y, z }
+    }
+}
+
+impl FromStr for Vector3 {
+    type Err = anyhow::Error;
+
+    /// Parses `(x, y, z)`, the format the game writes, with or without the parentheses.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        let inner = trimmed
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .unwrap_or(trimmed);
+
+        let components: Vec<&str> = inner.split(',').map(str::trim).collect();
+        let [x, y, z] = components[..] else {
+            return Err(anyhow!("Expected 3 vector components, got '{}'", value));
+        };
+
+        let component = |component: &str| {
+            component
+                .parse::<f32>()
+                .with_context(|| format!("Failed to parse vector component '{}'", component))
+        };
+
+        Ok(Vector3::new(component(x)?, component(y)?, component(z)?))
+    }
+}
@@ -0,0 +1,403 @@
+//! Filtering assets by their serialized fields, such as
+//! `type = Gun and player_damage.amount > 40 and firemodes contains Auto`.
+//!
+//! Fields are named by their path in the serialized asset, with inherited fields read like the
+//! asset's own, see [`fields`]. A [`Filter`] is one or more comparisons joined with `and`, `or`
+//! and `not`, grouped with parentheses:
+//!
+//! - `field = value` and `!=`, `<`, `<=`, `>`, `>=`. Text compares case-insensitively, and
+//!   numbers by value.
+//! - `field contains value` is a case-insensitive substring of text, or an element of a list.
+//! - `field matches regex`, or `field ~ regex`, matches text against a regular expression.
+//! - `field` alone is true for `true`, numbers other than `0`, and text or lists that are not
+//!   empty.
+//!
+//! Values are a single word, or quoted with `"` or `'` when they hold spaces. Assets without
+//! a field never match a comparison on it.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Context};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::parser::assets::Asset;
+
+/// Serialized fields of an asset by path, see [`fields`].
+pub type Fields = BTreeMap<String, Value>;
+
+/// Flattens the serialized asset into dotted paths, such as `player_damage.amount`.
+///
+/// Inherited fields are nested under `base`, which is left out so they read like the asset's
+/// own. Lists have no fixed set of paths, and are kept whole.
+pub fn fields(asset: &Asset) -> Fields {
+    let mut fields = Fields::new();
+    // Assets only hold plain data, which always serializes.
+    flatten(
+        None,
+        serde_json::to_value(asset).unwrap_or_default(),
+        &mut fields,
+    );
+
+    fields
+}
+
+fn flatten(prefix: Option<&str>, value: Value, fields: &mut Fields) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let path = match (prefix, key.as_str()) {
+                    (prefix, "base") => prefix.map(str::to_string),
+                    (Some(prefix), key) => Some(format!("{}.{}", prefix, key)),
+                    (None, key) => Some(key.to_string()),
+                };
+
+                flatten(path.as_deref(), value, fields);
+            }
+        }
+        value => {
+            if let Some(prefix) = prefix {
+                fields.insert(prefix.to_string(), value);
+            }
+        }
+    }
+}
+
+/// Orders two field values for sorting: numbers by value, text case-insensitively, and
+/// missing values last.
+pub fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Greater,
+        (_, Value::Null) => Ordering::Less,
+        (Value::Number(a), Value::Number(b)) => {
+            let (a, b) = (
+                a.as_f64().unwrap_or_default(),
+                b.as_f64().unwrap_or_default(),
+            );
+            a.total_cmp(&b)
+        }
+        (Value::String(a), Value::String(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (a, b) => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+/// Text of a field value, with `none` for missing values.
+///
+/// Most fields are `f32`, which are shown as such so `40` reads as `40` and `0.2` does not
+/// read as `0.20000000298023224`.
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => "none".to_string(),
+        Value::String(text) => text.clone(),
+        Value::Number(number) if number.is_f64() => {
+            let number = number.as_f64().unwrap_or_default();
+            if number as f32 as f64 == number {
+                (number as f32).to_string()
+            } else {
+                number.to_string()
+            }
+        }
+        value => value.to_string(),
+    }
+}
+
+/// A parsed filter expression, see the [module documentation](self).
+#[derive(Debug, Clone)]
+pub struct Filter {
+    expr: Expr,
+}
+
+impl Filter {
+    pub fn matches(&self, fields: &Fields) -> bool {
+        self.expr.eval(fields)
+    }
+
+    /// Whether `asset` matches, see [`matches`](Self::matches).
+    pub fn matches_asset(&self, asset: &Asset) -> bool {
+        self.matches(&fields(asset))
+    }
+}
+
+impl FromStr for Filter {
+    type Err = anyhow::Error;
+
+    fn from_str(text: &str) -> anyhow::Result<Filter> {
+        let tokens = tokenize(text)?;
+        let mut parser = FilterParser { tokens, next: 0 };
+
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            bail!("Unexpected {} after the end of the filter", token);
+        }
+
+        Ok(Filter { expr })
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Compare(String, Op, String),
+    Matches(String, Regex),
+    Truthy(String),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+impl Expr {
+    fn eval(&self, fields: &Fields) -> bool {
+        match self {
+            Expr::And(a, b) => a.eval(fields) && b.eval(fields),
+            Expr::Or(a, b) => a.eval(fields) || b.eval(fields),
+            Expr::Not(expr) => !expr.eval(fields),
+            Expr::Compare(field, op, literal) => match fields.get(field) {
+                None | Some(Value::Null) => false,
+                Some(value) => compare(value, *op, literal),
+            },
+            Expr::Matches(field, regex) => match fields.get(field) {
+                Some(Value::String(text)) => regex.is_match(text),
+                Some(Value::Array(items)) => items
+                    .iter()
+                    .any(|item| item.as_str().is_some_and(|text| regex.is_match(text))),
+                _ => false,
+            },
+            Expr::Truthy(field) => match fields.get(field) {
+                Some(Value::Bool(value)) => *value,
+                Some(Value::Number(number)) => number.as_f64() != Some(0.0),
+                Some(Value::String(text)) => !text.is_empty(),
+                Some(Value::Array(items)) => !items.is_empty(),
+                _ => false,
+            },
+        }
+    }
+}
+
+fn compare(value: &Value, op: Op, literal: &str) -> bool {
+    match op {
+        Op::Eq => equals(value, literal),
+        Op::Ne => !equals(value, literal),
+        Op::Contains => match value {
+            Value::String(text) => text.to_lowercase().contains(&literal.to_lowercase()),
+            Value::Array(items) => items.iter().any(|item| equals(item, literal)),
+            _ => false,
+        },
+        Op::Lt | Op::Le | Op::Gt | Op::Ge => {
+            let ordering = match value {
+                Value::Number(number) => compare_number(number, literal),
+                Value::String(text) => Some(text.to_lowercase().cmp(&literal.to_lowercase())),
+                _ => None,
+            };
+
+            ordering.is_some_and(|ordering| match op {
+                Op::Lt => ordering.is_lt(),
+                Op::Le => ordering.is_le(),
+                Op::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            })
+        }
+    }
+}
+
+fn equals(value: &Value, literal: &str) -> bool {
+    match value {
+        Value::Number(number) => compare_number(number, literal).is_some_and(Ordering::is_eq),
+        Value::String(text) => text.eq_ignore_ascii_case(literal),
+        Value::Bool(value) => literal
+            .to_lowercase()
+            .parse::<bool>()
+            .is_ok_and(|literal| *value == literal),
+        // Enum variants with data, such as `{"Burst": 3}`, are matched by their name.
+        Value::Object(object) if object.len() == 1 => object
+            .keys()
+            .next()
+            .is_some_and(|name| name.eq_ignore_ascii_case(literal)),
+        _ => false,
+    }
+}
+
+/// Fractions are compared as `f32`, like most fields are, or `0.2` would be less than the
+/// `0.20000000298023224` a field set to `0.2` serializes as.
+fn compare_number(number: &serde_json::Number, literal: &str) -> Option<Ordering> {
+    let literal = literal.parse::<f64>().ok()?;
+    let number = number.as_f64()?;
+
+    if number.fract() != 0.0 || literal.fract() != 0.0 {
+        (number as f32).partial_cmp(&(literal as f32))
+    } else {
+        number.partial_cmp(&literal)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "`{}`", word),
+            Token::Quoted(text) => write!(f, "\"{}\"", text),
+            Token::Op(op) => write!(f, "`{}`", op),
+            Token::Open => f.write_str("`(`"),
+            Token::Close => f.write_str("`)`"),
+        }
+    }
+}
+
+const OPS: [&str; 8] = ["<=", ">=", "!=", "==", "=", "<", ">", "~"];
+
+fn tokenize(text: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = text.trim_start();
+
+    while let Some(next) = rest.chars().next() {
+        if let Some(op) = OPS.iter().find(|op| rest.starts_with(**op)) {
+            tokens.push(Token::Op(op));
+            rest = &rest[op.len()..];
+        } else if next == '(' || next == ')' {
+            tokens.push(if next == '(' {
+                Token::Open
+            } else {
+                Token::Close
+            });
+            rest = &rest[1..];
+        } else if next == '"' || next == '\'' {
+            let end = rest[1..]
+                .find(next)
+                .with_context(|| format!("Missing closing {} in the filter", next))?;
+            tokens.push(Token::Quoted(rest[1..end + 1].to_string()));
+            rest = &rest[end + 2..];
+        } else {
+            let end = rest
+                .find(|c: char| c.is_whitespace() || "()=!<>~\"'".contains(c))
+                .unwrap_or(rest.len());
+            if end == 0 {
+                bail!("Unexpected `{}` in the filter", next);
+            }
+            tokens.push(Token::Word(rest[..end].to_string()));
+            rest = &rest[end..];
+        }
+
+        rest = rest.trim_start();
+    }
+
+    Ok(tokens)
+}
+
+struct FilterParser {
+    tokens: Vec<Token>,
+    next: usize,
+}
+
+impl FilterParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.next).cloned();
+        self.next += 1;
+        token
+    }
+
+    /// Consumes the next token if it is the keyword `keyword`.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword) => {
+                self.next += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn or(&mut self) -> anyhow::Result<Expr> {
+        let mut expr = self.and()?;
+        while self.keyword("or") {
+            expr = Expr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> anyhow::Result<Expr> {
+        let mut expr = self.not()?;
+        while self.keyword("and") {
+            expr = Expr::And(Box::new(expr), Box::new(self.not()?));
+        }
+
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> anyhow::Result<Expr> {
+        if self.keyword("not") {
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> anyhow::Result<Expr> {
+        let field = match self.advance() {
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                return match self.advance() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err(anyhow!("Missing `)` in the filter")),
+                };
+            }
+            Some(Token::Word(field)) => field,
+            Some(token) => bail!("Expected a field, found {}", token),
+            None => bail!("Expected a field at the end of the filter"),
+        };
+
+        let op = match self.peek() {
+            Some(Token::Op("~")) => None,
+            Some(Token::Op("=" | "==")) => Some(Op::Eq),
+            Some(Token::Op("!=")) => Some(Op::Ne),
+            Some(Token::Op("<")) => Some(Op::Lt),
+            Some(Token::Op("<=")) => Some(Op::Le),
+            Some(Token::Op(">")) => Some(Op::Gt),
+            Some(Token::Op(">=")) => Some(Op::Ge),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("contains") => Some(Op::Contains),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("matches") => None,
+            _ => return Ok(Expr::Truthy(field)),
+        };
+        self.next += 1;
+
+        let value = match self.advance() {
+            Some(Token::Word(value) | Token::Quoted(value)) => value,
+            Some(token) => bail!("Expected a value after `{}`, found {}", field, token),
+            None => bail!("Expected a value after `{}`", field),
+        };
+
+        Ok(match op {
+            Some(op) => Expr::Compare(field, op, value),
+            None => {
+                let regex = Regex::new(&value)
+                    .with_context(|| format!("Failed to parse regex for `{}`", field))?;
+                Expr::Matches(field, regex)
+            }
+        })
+    }
+}
@@ -0,0 +1,171 @@
+//! How many of each raiding tool it takes to destroy a barricade or structure.
+
+use crate::damage::ArmorTierMultipliers;
+use crate::parser::assets::barricade::{self, ItemBarricadeAsset};
+use crate::parser::assets::charge::ItemChargeAsset;
+use crate::parser::assets::magazine::ItemMagazineAsset;
+use crate::parser::assets::melee::ItemMeleeAsset;
+use crate::parser::assets::structure::{self, ItemStructureAsset};
+use crate::parser::assets::throwable::ItemThrowableAsset;
+use crate::parser::assets::{AsBaseAsset, AsWeapon};
+
+#[derive(Debug, Copy, Clone)]
+pub enum Buildable<'a> {
+    Barricade(&'a ItemBarricadeAsset),
+    Structure(&'a ItemStructureAsset),
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum RaidTool<'a> {
+    /// Counted in rounds fired, only `Explosive` magazines damage non-vulnerable buildables.
+    Magazine(&'a ItemMagazineAsset),
+
+    /// Counted in throws.
+    Throwable(&'a ItemThrowableAsset),
+
+    /// Counted in charges detonated.
+    Charge(&'a ItemChargeAsset),
+
+    /// Counted in swings of the regular attack.
+    Melee(&'a ItemMeleeAsset),
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct RaidCost {
+    /// Name of the raiding tool.
+    pub tool: String,
+
+    /// Damage dealt to the buildable by one use of the tool.
+    pub damage: f32,
+
+    /// Number of uses needed to destroy the buildable, or `None` if the tool cannot damage it.
+    pub count: Option<u32>,
+}
+
+impl Buildable<'_> {
+    pub fn name(&self) -> &str {
+        match self {
+            Buildable::Barricade(barricade) => barricade.name(),
+            Buildable::Structure(structure) => structure.name(),
+        }
+    }
+
+    pub fn health(&self) -> u16 {
+        match self {
+            Buildable::Barricade(barricade) => barricade.health,
+            Buildable::Structure(structure) => structure.health,
+        }
+    }
+
+    /// Damage the buildable takes from one hit, after the game's rules are applied:
+    ///
+    /// - Buildables without `Vulnerable` only take damage from explosions.
+    /// - Structures with `Proof_Explosion` take no damage from explosions.
+    /// - Damage is multiplied by the server's multiplier for the buildable's `Armor_Tier`.
+    pub fn damage_taken(
+        &self,
+        barricade_damage: f32,
+        structure_damage: f32,
+        is_explosive: bool,
+        tiers: ArmorTierMultipliers,
+    ) -> f32 {
+        match self {
+            Buildable::Barricade(barricade) => {
+                if !barricade.is_vulnerable && !is_explosive {
+                    return 0.0;
+                }
+
+                let armor = match barricade.armor_tier {
+                    barricade::ArmorTier::Low => tiers.low_tier,
+                    barricade::ArmorTier::High => tiers.high_tier,
+                };
+                barricade_damage * armor
+            }
+            Buildable::Structure(structure) => {
+                if !structure.is_vulnerable && !is_explosive {
+                    return 0.0;
+                }
+                if is_explosive && structure.is_explosion_proof {
+                    return 0.0;
+                }
+
+                let armor = match structure.armor_tier {
+                    structure::ArmorTier::Low => tiers.low_tier,
+                    structure::ArmorTier::High => tiers.high_tier,
+                };
+                structure_damage * armor
+            }
+        }
+    }
+
+    /// Number of hits of `damage` needed to destroy the buildable.
+    pub fn hits_to_destroy(&self, damage: f32) -> Option<u32> {
+        // The game truncates damage to whole numbers before applying it.
+        let damage = damage.floor();
+
+        (damage > 0.0).then(|| (self.health() as f32 / damage).ceil() as u32)
+    }
+
+    /// Cost of destroying the buildable with each of `tools`, in the same order.
+    pub fn raid_costs(&self, tools: &[RaidTool], tiers: ArmorTierMultipliers) -> Vec<RaidCost> {
+        tools
+            .iter()
+            .map(|tool| {
+                let damage = tool.damage_against(self, tiers);
+
+                RaidCost {
+                    tool: tool.name().to_string(),
+                    damage,
+                    count: self.hits_to_destroy(damage),
+                }
+            })
+            .collect()
+    }
+}
+
+impl RaidTool<'_> {
+    pub fn name(&self) -> &str {
+        match self {
+            RaidTool::Magazine(magazine) => magazine.name(),
+            RaidTool::Throwable(throwable) => throwable.name(),
+            RaidTool::Charge(charge) => charge.name(),
+            RaidTool::Melee(melee) => melee.name(),
+        }
+    }
+
+    /// If the tool damages through an explosion.
+    pub fn is_explosive(&self) -> bool {
+        match self {
+            RaidTool::Magazine(magazine) => magazine.is_explosive,
+            RaidTool::Throwable(throwable) => throwable.is_explosive,
+            RaidTool::Charge(_) => true,
+            RaidTool::Melee(_) => false,
+        }
+    }
+
+    /// Damage one use of the tool deals to `buildable`.
+    pub fn damage_against(&self, buildable: &Buildable, tiers: ArmorTierMultipliers) -> f32 {
+        let (barricade_damage, structure_damage) = match self {
+            RaidTool::Magazine(magazine) if magazine.is_explosive => {
+                (magazine.barricade_damage, magazine.structure_damage)
+            }
+            RaidTool::Magazine(_) => (0.0, 0.0),
+            RaidTool::Throwable(throwable) => (
+                throwable.weapon().barricade_damage,
+                throwable.weapon().structure_damage,
+            ),
+            RaidTool::Charge(charge) => (charge.barricade_damage, charge.structure_damage),
+            RaidTool::Melee(melee) => (
+                melee.weapon().barricade_damage,
+                melee.weapon().structure_damage,
+            ),
+        };
+
+        buildable.damage_taken(
+            barricade_damage,
+            structure_damage,
+            self.is_explosive(),
+            tiers,
+        )
+    }
+}
@@ -0,0 +1,197 @@
+//! Where asset files are read from, so the crate can parse content that is not on disk.
+//!
+//! Everything that reads a file goes through an [`AssetSource`]: [`FileSystem`] for folders on
+//! disk, and [`MemorySource`] for files held in memory, such as a mod upload. With the `zip` and
+//! `tar` features, a [`MemorySource`] can be filled from an archive.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+
+/// A tree of files that assets are read from.
+///
+/// Paths use the same layout as on disk, e.g. `Items/Guns/Maplestrike/English.dat`.
+pub trait AssetSource {
+    fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>>;
+
+    fn read_to_string(&self, path: &Path) -> anyhow::Result<String> {
+        let content = self.read(path)?;
+
+        String::from_utf8(content)
+            .with_context(|| format!("Failed to read content for: {}", path.display()))
+    }
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Lists the files and folders directly inside the folder at `path`.
+    fn read_dir(&self, path: &Path) -> anyhow::Result<Vec<PathBuf>>;
+}
+
+/// Reads from the filesystem with `std::fs`.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct FileSystem;
+
+impl AssetSource for FileSystem {
+    fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        std::fs::read(path)
+            .with_context(|| format!("Failed to read content for: {}", path.display()))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_dir(&self, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        let entries = std::fs::read_dir(path)
+            .with_context(|| format!("Failed to read directory: {}", path.display()))?;
+
+        entries.map(|entry| Ok(entry?.path())).collect()
+    }
+}
+
+/// Files held in memory, keyed by their path. Folders are implied by the paths of their files.
+#[derive(Debug, Default, Clone)]
+pub struct MemorySource {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemorySource {
+    pub fn new() -> MemorySource {
+        MemorySource::default()
+    }
+
+    /// Adds a file, replacing any earlier file at the same path.
+    pub fn insert(&mut self, path: impl AsRef<Path>, content: impl Into<Vec<u8>>) {
+        self.files.insert(normalize(path.as_ref()), content.into());
+    }
+
+    /// Adds a file, see [`insert`](Self::insert).
+    pub fn with(mut self, path: impl AsRef<Path>, content: impl Into<Vec<u8>>) -> MemorySource {
+        self.insert(path, content);
+        self
+    }
+
+    /// Paths of every file, in order.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Reads every file of a zip archive, skipping entries whose path leaves the archive.
+    #[cfg(feature = "zip")]
+    pub fn from_zip<R: std::io::Read + std::io::Seek>(reader: R) -> anyhow::Result<MemorySource> {
+        use std::io::Read;
+
+        let mut archive = zip::ZipArchive::new(reader).context("Failed to open zip archive")?;
+        let mut source = MemorySource::new();
+
+        for index in 0..archive.len() {
+            let mut file = archive
+                .by_index(index)
+                .with_context(|| format!("Failed to read zip entry {}", index))?;
+            let Some(path) = file.enclosed_name() else {
+                continue;
+            };
+            if file.is_dir() {
+                continue;
+            }
+
+            let mut content = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut content)
+                .with_context(|| format!("Failed to read content for: {}", path.display()))?;
+            source.insert(path, content);
+        }
+
+        Ok(source)
+    }
+
+    /// Reads every regular file of a tar archive, skipping entries whose path leaves the
+    /// archive. Compressed tarballs need to be decompressed by `reader`.
+    #[cfg(feature = "tar")]
+    pub fn from_tar<R: std::io::Read>(reader: R) -> anyhow::Result<MemorySource> {
+        use std::io::Read;
+
+        let mut archive = tar::Archive::new(reader);
+        let mut source = MemorySource::new();
+
+        for entry in archive.entries().context("Failed to open tar archive")? {
+            let mut entry = entry.context("Failed to read tar entry")?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+
+            let path = entry
+                .path()
+                .context("Failed to read tar entry path")?
+                .into_owned();
+            if !is_enclosed(&path) {
+                continue;
+            }
+
+            let mut content = Vec::new();
+            entry
+                .read_to_end(&mut content)
+                .with_context(|| format!("Failed to read content for: {}", path.display()))?;
+            source.insert(path, content);
+        }
+
+        Ok(source)
+    }
+}
+
+impl AssetSource for MemorySource {
+    fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        self.files
+            .get(&normalize(path))
+            .cloned()
+            .ok_or_else(|| anyhow!("Failed to read content for: {}", path.display()))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(&normalize(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        let path = normalize(path);
+
+        self.files
+            .keys()
+            .any(|file| file != &path && file.starts_with(&path))
+    }
+
+    fn read_dir(&self, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        if !self.is_dir(path) {
+            return Err(anyhow!("Failed to read directory: {}", path.display()));
+        }
+
+        let directory = normalize(path);
+        let children: BTreeSet<PathBuf> = self
+            .files
+            .keys()
+            .filter_map(|file| file.strip_prefix(&directory).ok())
+            .filter_map(|rest| rest.components().next())
+            .map(|child| path.join(child))
+            .collect();
+
+        Ok(children.into_iter().collect())
+    }
+}
+
+/// Drops `.` components, so `./Items` and `Items` are the same file.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+#[cfg(feature = "tar")]
+fn is_enclosed(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
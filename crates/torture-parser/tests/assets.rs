@@ -0,0 +1,140 @@
+//! The `As*` traits, the unified [`Asset`] enum and the serialized layout of inherited fields.
+
+mod common;
+
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use torture_parser::parser::assets::glasses::ItemGlassesAsset;
+use torture_parser::parser::assets::gun::ItemGunAsset;
+use torture_parser::parser::assets::sentry::ItemSentryAsset;
+use torture_parser::parser::assets::shirt::ItemShirtAsset;
+use torture_parser::parser::assets::sight::ItemSightAsset;
+use torture_parser::parser::assets::{
+    AsBarricade, AsBaseAsset, AsCaliber, AsClothing, AsWeapon, Asset,
+};
+use torture_parser::source::{AssetSource, MemorySource};
+
+use common::{parse, read};
+
+fn armor<T: AsClothing>(asset: &T) -> f32 {
+    asset.clothing().armor
+}
+
+#[test]
+fn views_reach_through_parents() {
+    let shirt: ItemShirtAsset = parse("Police_Top");
+    assert_eq!(
+        shirt.name(),
+        shirt.item_bag_asset.item_clothing_asset.base_asset.name
+    );
+    assert_eq!(
+        armor(&shirt),
+        shirt.item_bag_asset.item_clothing_asset.armor
+    );
+
+    let glasses: ItemGlassesAsset = parse("Military_Nightvision");
+    assert_eq!(
+        armor(&glasses),
+        glasses.item_gear_asset.item_clothing_asset.armor
+    );
+
+    let gun: ItemGunAsset = parse("Maplestrike");
+    assert_eq!(gun.weapon().range, gun.item_weapon_asset.range);
+    assert_eq!(gun.id(), gun.item_weapon_asset.base_asset.id);
+
+    let sight: ItemSightAsset = parse("Eight_Power_Scope");
+    assert_eq!(sight.caliber().calibers, sight.item_caliber_asset.calibers);
+
+    let mut sentry: ItemSentryAsset = parse("Sentry");
+    sentry.barricade_mut().health = 1;
+    assert_eq!(sentry.item_storage_asset.item_barricade_asset.health, 1);
+}
+
+#[test]
+fn inherited_fields_layout() {
+    let gun: ItemGunAsset = parse("Maplestrike");
+    let value = serde_json::to_value(&gun).unwrap();
+
+    assert_eq!(value["base"]["base"]["name"], gun.name());
+    assert_eq!(value["base"]["range"], gun.weapon().range);
+    assert!(value.get("name").is_none());
+
+    let parsed: ItemGunAsset = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+}
+
+#[test]
+fn asset_kinds() {
+    let cases = [
+        ("Maplestrike", "gun"),
+        ("Police_Top", "shirt"),
+        ("Military_Nightvision", "glasses"),
+        ("Metal_Locker", "storage"),
+        ("Sentry", "sentry"),
+        ("Bandage", "consumable"),
+    ];
+
+    for (fixture, kind) in cases {
+        let asset: Asset = parse(fixture);
+        assert_eq!(asset.kind(), kind, "{}", fixture);
+        assert!(Asset::KINDS.contains(&kind));
+
+        let value = serde_json::to_value(&asset).unwrap();
+        assert_eq!(value["kind"], kind, "{}", fixture);
+    }
+
+    let gun: Asset = parse("Maplestrike");
+    assert_eq!(gun.name(), "Maplestrike");
+    let Asset::Gun(gun) = gun else {
+        panic!("Maplestrike is a gun");
+    };
+    assert!(gun.weapon().player_damage.skull_multiplier > 0.0);
+}
+
+/// Records every file read from the wrapped source.
+struct Recorded {
+    source: MemorySource,
+    reads: RefCell<Vec<PathBuf>>,
+}
+
+impl AssetSource for Recorded {
+    fn read(&self, path: &Path) -> anyhow::Result<Vec<u8>> {
+        self.reads.borrow_mut().push(path.to_path_buf());
+        self.source.read(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.source.is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.source.is_dir(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+        self.source.read_dir(path)
+    }
+}
+
+#[test]
+fn localization_is_read_once() {
+    let source = Recorded {
+        source: MemorySource::new().with("Maplestrike/English.dat", "Name Maplestrike\n"),
+        reads: RefCell::default(),
+    };
+    let content = read("Maplestrike");
+
+    let (gun, _) = Asset::parse_from(&source, Path::new("Maplestrike"), &content).unwrap();
+    assert!(gun.base_asset().localized);
+    assert_eq!(
+        *source.reads.borrow(),
+        [Path::new("Maplestrike/English.dat")]
+    );
+
+    // Without an `English.dat` the asset has no name, which lints report.
+    let (gun, _) =
+        Asset::parse_from(&MemorySource::new(), Path::new("Maplestrike"), &content).unwrap();
+    assert!(!gun.base_asset().localized);
+    assert!(gun.name().is_empty());
+}
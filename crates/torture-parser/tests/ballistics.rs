@@ -0,0 +1,62 @@
+//! Simulating the Maplestrike fixture, which has 20 steps of 10 meters and a drop of 0.003.
+
+mod common;
+
+use torture_parser::ballistics::BallisticsSimulation;
+use torture_parser::parser::assets::barrel::ItemBarrelAsset;
+use torture_parser::parser::assets::gun::ItemGunAsset;
+use torture_parser::parser::assets::sight::ItemSightAsset;
+
+use common::{assert_close, parse};
+
+/// Distances, drops and times are checked to the millimeter and millisecond.
+const TOLERANCE: f32 = 1e-3;
+
+#[test]
+fn drop_table() {
+    let gun: ItemGunAsset = parse("Maplestrike");
+    let simulation = BallisticsSimulation::simulate(&gun, None);
+
+    assert_eq!(simulation.points.len(), 21);
+    assert_close(simulation.max_range(), 199.889, TOLERANCE);
+
+    // Every step falls by the drop of all steps before it.
+    let table = simulation.table(50.0);
+    let drops: Vec<f32> = table.iter().map(|point| point.drop).collect();
+    assert_eq!(drops.len(), 3);
+    assert_close(drops[0], 0.300, TOLERANCE);
+    assert_close(drops[1], 1.350, TOLERANCE);
+    assert_close(table[1].time, 0.2, TOLERANCE);
+    assert!(simulation.at(200.0).is_none());
+
+    // Falls 0.3 meters at the fifth step, at 50 meters.
+    assert_close(simulation.effective_range(0.3), 49.999, TOLERANCE);
+    assert_close(
+        simulation.effective_range(100.0),
+        simulation.max_range(),
+        TOLERANCE,
+    );
+}
+
+#[test]
+fn barrel_and_sight() {
+    let gun: ItemGunAsset = parse("Maplestrike");
+    let barrel: ItemBarrelAsset = parse("Suppressor");
+    let plain = BallisticsSimulation::simulate(&gun, None);
+    let suppressed = BallisticsSimulation::simulate(&gun, Some(&barrel));
+
+    // A drop multiplier of 1.2 makes the bullet fall 1.2 times as far.
+    let plain_drop = plain.at(100.0).unwrap().drop;
+    assert_close(
+        suppressed.at(100.0).unwrap().drop,
+        plain_drop * 1.2,
+        TOLERANCE,
+    );
+
+    // The 8x scope marks 200 meters, which the bullet never reaches.
+    let scope: ItemSightAsset = parse("Eight_Power_Scope");
+    let markers = plain.markers(&scope);
+    assert_eq!(markers.len(), 2);
+    assert_close(markers[0].1.unwrap().drop, plain_drop, TOLERANCE);
+    assert!(markers[1].1.is_none());
+}
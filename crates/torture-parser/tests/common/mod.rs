@@ -0,0 +1,43 @@
+//! Reading the fixtures in `tests/fixtures`, shared by the integration tests.
+
+// Each test uses only some of these.
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+
+use torture_parser::parser::Parser;
+
+/// The `tests` folder of the crate.
+pub fn tests_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests")
+}
+
+/// Folder of a fixture, such as `tests/fixtures/Russia`.
+pub fn fixture_dir(fixture: &str) -> PathBuf {
+    tests_dir().join("fixtures").join(fixture)
+}
+
+/// Reads a file in the folder of a fixture.
+pub fn read_file(fixture: &str, file: &str) -> String {
+    std::fs::read_to_string(fixture_dir(fixture).join(file)).unwrap()
+}
+
+/// Reads the data file of a fixture, which is named after its folder.
+pub fn read(fixture: &str) -> String {
+    read_file(fixture, &format!("{}.dat", fixture))
+}
+
+/// Parses the data file of a fixture.
+pub fn parse<T: Parser<T>>(fixture: &str) -> T {
+    T::parse(&fixture_dir(fixture), &read(fixture)).unwrap()
+}
+
+/// Checks that `actual` is within `tolerance` of `expected`.
+pub fn assert_close(actual: f32, expected: f32, tolerance: f32) {
+    assert!(
+        (actual - expected).abs() < tolerance,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
@@ -0,0 +1,140 @@
+//! Damage of the Maplestrike, Machete and Frag_Grenade fixtures against players, zombies and
+//! buildables.
+//!
+//! The Maplestrike fires every 5 ticks, so each shot after the first adds 0.1 seconds.
+
+mod common;
+
+use torture_parser::damage::{
+    ArmorTierMultipliers, DamageSource, HitDamage, HitLocation, PlayerArmor, Target,
+};
+use torture_parser::parser::assets::barrel::ItemBarrelAsset;
+use torture_parser::parser::assets::barricade::ItemBarricadeAsset;
+use torture_parser::parser::assets::gear::ItemGearAsset;
+use torture_parser::parser::assets::gun::ItemGunAsset;
+use torture_parser::parser::assets::magazine::ItemMagazineAsset;
+use torture_parser::parser::assets::melee::ItemMeleeAsset;
+use torture_parser::parser::assets::shirt::ItemShirtAsset;
+use torture_parser::parser::assets::storage::ItemStorageAsset;
+use torture_parser::parser::assets::structure::ItemStructureAsset;
+use torture_parser::parser::assets::throwable::ItemThrowableAsset;
+use torture_parser::raid::Buildable;
+
+use common::parse;
+
+/// Checks the damage, shots-to-kill and time-to-kill of each hit, in order.
+fn assert_hits(hits: &[HitDamage], expected: &[(f32, Option<u32>, Option<f32>)]) {
+    assert_eq!(hits.len(), expected.len());
+
+    for (hit, &(damage, shots, time)) in hits.iter().zip(expected) {
+        assert_eq!(hit.damage, damage, "{:?}", hit);
+        assert_eq!(hit.shots_to_kill, shots, "{:?}", hit);
+        match (hit.time_to_kill, time) {
+            (Some(actual), Some(time)) => assert!((actual - time).abs() < 1e-4, "{:?}", hit),
+            (actual, time) => assert_eq!(actual, time, "{:?}", hit),
+        }
+    }
+}
+
+#[test]
+fn against_player() {
+    let gun: ItemGunAsset = parse("Maplestrike");
+    let magazine: ItemMagazineAsset = parse("Military_Magazine");
+    let helmet: ItemGearAsset = parse("Military_Helmet");
+    let top: ItemShirtAsset = parse("Police_Top");
+
+    // Player_Damage 40, times the skull, spine, arm and leg multipliers.
+    let source = DamageSource::gun(&gun, &[], None);
+    let hits = source.against(&Target::Player(PlayerArmor::default()), 0.0);
+    let locations: Vec<_> = hits.iter().map(|hit| hit.location).collect();
+    assert_eq!(
+        locations,
+        [
+            Some(HitLocation::Skull),
+            Some(HitLocation::Spine),
+            Some(HitLocation::Arm),
+            Some(HitLocation::Leg),
+        ]
+    );
+    assert_hits(
+        &hits,
+        &[
+            (44.0, Some(3), Some(0.2)),
+            (32.0, Some(4), Some(0.3)),
+            (24.0, Some(5), Some(0.4)),
+            (24.0, Some(5), Some(0.4)),
+        ],
+    );
+
+    // The magazine multiplies damage by 1.05, the helmet's `Armor` protects the skull and the
+    // top's the spine and arms, and each hit is cut down to whole damage.
+    let source = DamageSource::gun(&gun, &[], Some(&magazine));
+    let armor = PlayerArmor {
+        hat: Some(&helmet.item_clothing_asset),
+        shirt: Some(&top.item_bag_asset.item_clothing_asset),
+        ..PlayerArmor::default()
+    };
+    assert_hits(
+        &source.against(&Target::Player(armor), 0.0),
+        &[
+            (27.0, Some(4), Some(0.3)),
+            (30.0, Some(4), Some(0.3)),
+            (22.0, Some(5), Some(0.4)),
+            (25.0, Some(4), Some(0.3)),
+        ],
+    );
+}
+
+#[test]
+fn against_zombie() {
+    let gun: ItemGunAsset = parse("Maplestrike");
+    let magazine: ItemMagazineAsset = parse("Military_Magazine");
+    let suppressor: ItemBarrelAsset = parse("Suppressor");
+
+    let source = DamageSource::gun(&gun, &[&suppressor.item_caliber_asset], Some(&magazine));
+    let hits = source.against(&Target::Zombie { health: 118 }, 0.0);
+
+    // A leg hit is 29.6 damage, which would take 4 hits to deal 118, but only 29 is applied.
+    assert_hits(
+        &hits,
+        &[
+            (108.0, Some(2), Some(0.1)),
+            (59.0, Some(2), Some(0.1)),
+            (29.0, Some(5), Some(0.4)),
+            (29.0, Some(5), Some(0.4)),
+        ],
+    );
+}
+
+#[test]
+fn against_buildable() {
+    let gun: ItemGunAsset = parse("Maplestrike");
+    let machete: ItemMeleeAsset = parse("Machete");
+    let grenade: ItemThrowableAsset = parse("Frag_Grenade");
+    let plate: ItemBarricadeAsset = parse("Wooden_Plate");
+    let locker: ItemStorageAsset = parse("Metal_Locker");
+    let wall: ItemStructureAsset = parse("Metal_Wall");
+
+    let tiers = ArmorTierMultipliers::default();
+    let plate = Target::Buildable(Buildable::Barricade(&plate), tiers);
+    let locker = Target::Buildable(Buildable::Barricade(&locker.item_barricade_asset), tiers);
+    let wall = Target::Buildable(Buildable::Structure(&wall), tiers);
+
+    let gun = DamageSource::gun(&gun, &[], None);
+    let machete = DamageSource::melee(&machete);
+    let grenade = DamageSource::throwable(&grenade);
+
+    // The wooden plate is `Vulnerable` and low tier, so guns and melee weapons damage it fully.
+    let hits = gun.against(&plate, 0.0);
+    assert_eq!(hits[0].location, None);
+    assert_hits(&hits, &[(20.0, Some(20), Some(1.9))]);
+    assert_hits(&machete.against(&plate, 0.0), &[(15.0, Some(27), None)]);
+
+    // The metal locker is only damaged by explosions, halved by its high tier.
+    assert_hits(&gun.against(&locker, 0.0), &[(0.0, None, None)]);
+    assert_hits(&grenade.against(&locker, 0.0), &[(150.0, Some(6), None)]);
+
+    // The metal wall is neither `Vulnerable` nor damaged by explosions.
+    assert_hits(&machete.against(&wall, 0.0), &[(0.0, None, None)]);
+    assert_hits(&grenade.against(&wall, 0.0), &[(0.0, None, None)]);
+}
@@ -0,0 +1,274 @@
+//! Writes every fixture back with `#[derive(DatAsset)]` and checks that parsing the output
+//! gives the same asset, along with the value readers the derive relies on.
+
+mod common;
+
+use torture_parser::parser::assets::caliber::ItemCaliberAsset;
+use torture_parser::parser::assets::clothing::ItemClothingAsset;
+use torture_parser::parser::assets::consumable::ItemConsumableAsset;
+use torture_parser::parser::assets::glasses::ItemGlassesAsset;
+use torture_parser::parser::assets::gun::{Firemode, ItemGunAsset};
+use torture_parser::parser::assets::magazine::ItemMagazineAsset;
+use torture_parser::parser::assets::melee::ItemMeleeAsset;
+use torture_parser::parser::assets::sentry::{ItemSentryAsset, Mode};
+use torture_parser::parser::assets::sight::ItemSightAsset;
+use torture_parser::parser::assets::structure::ItemStructureAsset;
+use torture_parser::parser::assets::tactical::ItemTacticalAsset;
+use torture_parser::parser::assets::{Rarity, Type};
+use torture_parser::parser::dat::{DatAsset, DatField, DatFieldKind, FromDatValue, ToDatValue};
+use torture_parser::parser::guid::Guid;
+use torture_parser::parser::reference::AssetReference;
+use torture_parser::parser::vector::Vector3;
+use torture_parser::parser::Parser;
+
+use common::{fixture_dir, read};
+
+fn assert_round_trip<T: Parser<T> + DatAsset + serde::Serialize>(fixture: &str) {
+    let directory = fixture_dir(fixture);
+    let asset = T::parse(&directory, &read(fixture)).unwrap();
+    let mut written = String::new();
+    asset.write_dat(&mut written);
+    let reparsed = T::parse(&directory, &written).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&asset).unwrap(),
+        serde_json::to_value(&reparsed).unwrap(),
+        "{} changed after writing it back:\n{}",
+        fixture,
+        written
+    );
+}
+
+macro_rules! round_trip {
+    ($name:ident, $type:ty, $fixture:literal) => {
+        #[test]
+        fn $name() {
+            assert_round_trip::<$type>($fixture);
+        }
+    };
+}
+
+round_trip!(gun, ItemGunAsset, "Maplestrike");
+round_trip!(caliber, ItemCaliberAsset, "Military_Magazine");
+round_trip!(explosive_magazine, ItemMagazineAsset, "Explosive_Magazine");
+round_trip!(sight, ItemSightAsset, "Eight_Power_Scope");
+round_trip!(sight_legacy, ItemSightAsset, "Holographic_Sight");
+round_trip!(tactical, ItemTacticalAsset, "Tactical_Light");
+round_trip!(melee_light, ItemMeleeAsset, "Flashlight_Baton");
+round_trip!(structure, ItemStructureAsset, "Metal_Wall");
+round_trip!(clothing, ItemClothingAsset, "Military_Helmet");
+round_trip!(glasses_headlamp, ItemGlassesAsset, "Headlamp");
+round_trip!(consumable_blueprints, ItemConsumableAsset, "Bandage");
+
+#[test]
+fn schema() {
+    let schema = ItemGunAsset::schema();
+
+    let damage = schema
+        .iter()
+        .find(|field| field.key == "Player_Skull_Multiplier")
+        .expect("flattened weapon keys are part of the schema");
+    assert_eq!(damage.value_type, "f32");
+    assert_eq!(damage.default, Some("1.0"));
+
+    let turret = schema.iter().find(|field| field.key == "Turret").unwrap();
+    assert_eq!(turret.kind, DatFieldKind::Flag);
+
+    // Keys read by `finish` functions are declared on the struct.
+    let hook = schema
+        .iter()
+        .find(|field| field.key == "Hook_Sight")
+        .unwrap();
+    assert_eq!(hook.kind, DatFieldKind::Finish);
+    assert_eq!(hook.description, "Read by `read_lists`.");
+    assert!(ItemMeleeAsset::schema()
+        .iter()
+        .any(|field| field.key == "SpotLight_Range"));
+
+    // Defaults are shown as written, including those that refer to other fields.
+    let default = |schema: Vec<DatField>, key| {
+        schema
+            .into_iter()
+            .find(|field| field.key == key)
+            .and_then(|field| field.default)
+    };
+    assert_eq!(
+        default(ItemClothingAsset::schema(), "Armor_Explosion"),
+        Some("item.armor")
+    );
+    assert_eq!(
+        default(ItemCaliberAsset::schema(), "Ballistic_Damage_Multiplier"),
+        Some("if item.damage != 0.0 { item.damage } else { 1.0 }")
+    );
+    assert_eq!(
+        default(ItemTacticalAsset::schema(), "Laser_Color"),
+        Some("Color::rgb(255, 0, 0)")
+    );
+
+    let mut keys: Vec<_> = schema.iter().map(|field| field.key).collect();
+    keys.sort_unstable();
+    keys.dedup();
+    assert_eq!(keys.len(), schema.len(), "keys are unique");
+}
+
+#[test]
+fn unknown_values_are_diagnostics() {
+    let content = "\
+GUID abd09f48f77b63658521404a94641e7
+Type sentry
+Rarity Ledendary
+ID 1244
+Armor_Tier HIGH
+Mode Hostlie
+";
+
+    let (sentry, diagnostics) =
+        ItemSentryAsset::parse_with_diagnostics(&fixture_dir("Sentry"), content).unwrap();
+    let base = &sentry.item_storage_asset.item_barricade_asset.base_asset;
+
+    assert!(base.guid.is_nil());
+    assert!(matches!(base.r#type, Type::Sentry));
+    assert!(matches!(base.rarity, Rarity::Common));
+    assert!(matches!(sentry.mode, Mode::Neutral));
+
+    let keys: Vec<_> = diagnostics.iter().map(|d| d.key.as_str()).collect();
+    assert_eq!(keys, ["GUID", "Rarity", "Mode"]);
+    assert_eq!(
+        diagnostics[2].message,
+        "Unknown Mode 'Hostlie', expected one of Neutral, Friendly, Hostile"
+    );
+}
+
+#[test]
+fn unparsed_numbers_are_diagnostics() {
+    let content = "\
+Type Gun
+Firerate fast
+Magazine_Replacements 2
+Magazine_Replacement_0_Map Russia
+Magazine_Replacement_0_ID magazine
+Magazine_Replacement_1_Map Germany
+Magazine_Replacement_1_ID 1165
+Burst abc
+Magazine_Calibers 2
+Magazine_Caliber_0 big
+Magazine_Caliber_1 4
+";
+
+    let (gun, diagnostics) =
+        ItemGunAsset::parse_with_diagnostics(&fixture_dir("Maplestrike"), content).unwrap();
+
+    assert_eq!(gun.firerate, 0);
+    assert!(gun.magazine_replacements[0].id.is_none());
+    assert_eq!(gun.magazine_replacements[1].id.id(), Some(1165));
+    assert!(matches!(gun.firemodes[..], [Firemode::Burst(0)]));
+    assert_eq!(gun.magazine_calibers, [4]);
+
+    let keys: Vec<_> = diagnostics.iter().map(|d| d.key.as_str()).collect();
+    assert_eq!(
+        keys,
+        [
+            "Firerate",
+            "Burst",
+            "Magazine_Replacement_0_ID",
+            "Magazine_Caliber_0"
+        ]
+    );
+    assert!(
+        diagnostics[0].message.starts_with("'fast' is not a number"),
+        "{}",
+        diagnostics[0]
+    );
+}
+
+#[test]
+fn unparsed_booleans_are_diagnostics() {
+    let content = "\
+Type Gun
+Can_Aim_During_Sprint yes
+";
+
+    let (gun, diagnostics) =
+        ItemGunAsset::parse_with_diagnostics(&fixture_dir("Maplestrike"), content).unwrap();
+
+    assert!(!gun.can_aim_during_sprint);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].key, "Can_Aim_During_Sprint");
+    assert_eq!(diagnostics[0].message, "'yes' is not true or false");
+}
+
+#[test]
+fn unreadable_values_keep_defaults() {
+    let content = "\
+Type Magazine
+Recoil_X foo
+Recoil_Y 0.5
+";
+
+    let (caliber, diagnostics) =
+        ItemCaliberAsset::parse_with_diagnostics(&fixture_dir("Military_Magazine"), content)
+            .unwrap();
+
+    // Recoil_X is reported and left at the default of 1, as if it were missing.
+    assert_eq!(caliber.recoil_x, 1.0);
+    assert_eq!(caliber.recoil_y, 0.5);
+    assert_eq!(caliber.aiming_recoil_multiplier, 1.0);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].key, "Recoil_X");
+}
+
+#[test]
+fn enum_values() {
+    for value in ["Arrest_Start", "arrest_start", "ARRESTSTART", "ArrestStart"] {
+        assert!(matches!(
+            Type::from_dat_value(value).unwrap(),
+            Type::ArrestStart
+        ));
+    }
+    assert_eq!(
+        Type::VehicleRepairTool.to_dat_value(),
+        "Vehicle_Repair_Tool"
+    );
+    assert!(Rarity::from_dat_value("").is_err());
+}
+
+#[test]
+fn guids_and_references() {
+    let guid: Guid = "7691f71f-76e9-26e5-d981-d7e76ed779fc".parse().unwrap();
+    assert_eq!(guid.to_string(), "7691f71f76e926e5d981d7e76ed779fc");
+    assert!("7691f71f76e926e5d981d7e76ed779f".parse::<Guid>().is_err());
+    assert!("7691f71f76e926e5d981d7e76ed779fg".parse::<Guid>().is_err());
+
+    let by_id = AssetReference::<ItemMagazineAsset>::from_dat_value("1244").unwrap();
+    assert_eq!(by_id.id(), Some(1244));
+    assert!(by_id.is(1244, Guid::NIL));
+
+    let by_guid = AssetReference::<ItemMagazineAsset>::from_dat_value(&guid.to_string()).unwrap();
+    assert_eq!(by_guid.guid(), Some(guid));
+    assert_eq!(
+        serde_json::to_string(&by_guid).unwrap(),
+        format!("\"{}\"", guid)
+    );
+
+    assert!(AssetReference::<ItemMagazineAsset>::from_dat_value("0")
+        .unwrap()
+        .is_none());
+    assert!(AssetReference::<ItemMagazineAsset>::from_dat_value("magazine").is_err());
+
+    // 32 digits are a GUID, even when they are all decimal.
+    let by_digits =
+        AssetReference::<ItemMagazineAsset>::from_dat_value("00000000000000000000000000001244")
+            .unwrap();
+    assert_eq!(by_digits.id(), None);
+    assert_eq!(by_digits.guid(), Some(Guid(0x1244)));
+}
+
+#[test]
+fn vectors() {
+    let expected = Vector3::new(1.0, -2.5, 0.0);
+
+    assert_eq!(Vector3::from_dat_value("(1, -2.5, 0)").unwrap(), expected);
+    assert_eq!(Vector3::from_dat_value("1,-2.5,0").unwrap(), expected);
+    assert_eq!(expected.to_dat_value(), "(1, -2.5, 0)");
+    assert!(Vector3::from_dat_value("(1, 2)").is_err());
+}
@@ -0,0 +1,99 @@
+//! Matching and comparing assets between two versions with [`AssetDiff`].
+
+mod common;
+
+use std::path::Path;
+
+use torture_parser::diff::AssetDiff;
+use torture_parser::parser::assets::Asset;
+use torture_parser::source::MemorySource;
+
+use common::{read, read_file};
+
+/// Parses a fixture after `edit` changes its data file and its `English.dat`.
+fn parse_edited(fixture: &str, edit: impl Fn(String) -> String) -> Asset {
+    let directory = Path::new(fixture);
+    let source = MemorySource::new().with(
+        directory.join("English.dat"),
+        edit(read_file(fixture, "English.dat")),
+    );
+    let content = edit(read(fixture));
+
+    Asset::parse_from(&source, directory, &content).unwrap().0
+}
+
+fn parse(fixture: &str) -> Asset {
+    parse_edited(fixture, |content| content)
+}
+
+fn versions() -> (Vec<Asset>, Vec<Asset>) {
+    let old = vec![parse("Maplestrike"), parse("Bandage"), parse("Machete")];
+    let new = vec![
+        parse("Military_Magazine"),
+        // Machete lost its GUID, so it is matched by ID.
+        parse_edited("Machete", |content| {
+            content
+                .lines()
+                .filter(|line| !line.starts_with("GUID"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }),
+        parse_edited("Maplestrike", |content| {
+            content
+                .replace("Player_Damage 40", "Player_Damage 42")
+                .replace("Name Maplestrike", "Name Maplestrike+")
+        }),
+    ];
+
+    (old, new)
+}
+
+#[test]
+fn matches_by_guid_then_id() {
+    let (old, new) = versions();
+    let diff = AssetDiff::new(&old, &new);
+
+    let added: Vec<_> = diff.added.iter().map(|asset| asset.name.as_str()).collect();
+    assert_eq!(added, ["Military Magazine"]);
+    let removed: Vec<_> = diff
+        .removed
+        .iter()
+        .map(|asset| asset.name.as_str())
+        .collect();
+    assert_eq!(removed, ["Bandage"]);
+
+    let changed: Vec<_> = diff
+        .changed
+        .iter()
+        .map(|change| (change.new.name.as_str(), change.is_renamed()))
+        .collect();
+    assert_eq!(changed, [("Maplestrike+", true), ("Machete", false)]);
+
+    let machete: Vec<_> = diff.changed[1]
+        .fields
+        .iter()
+        .map(|field| field.field.as_str())
+        .collect();
+    assert_eq!(machete, ["guid"]);
+}
+
+#[test]
+fn formats_changes() {
+    let (old, new) = versions();
+    let diff = AssetDiff::new(&old, &new);
+
+    let text = diff.to_string();
+    assert!(text.contains("renamed gun 363 (Maplestrike) → Maplestrike+\n"));
+    assert!(text.contains("Maplestrike+.player_damage.amount 40 → 42\n"));
+    assert!(text.contains("removed consumable 95 (Bandage)\n"));
+
+    let markdown = diff.markdown();
+    assert!(markdown.starts_with("## Changes\n"));
+    assert!(markdown.contains("\n### Removed\n\n- consumable 95 (Bandage)\n"));
+    assert!(markdown.contains("\n#### Maplestrike+\n\n- `player_damage.amount`: 40 → 42\n"));
+
+    let json = serde_json::to_value(&diff).unwrap();
+    assert_eq!(json["changed"][0]["fields"][0]["old"], 40.0);
+
+    assert!(AssetDiff::new(&old, &old).is_empty());
+}
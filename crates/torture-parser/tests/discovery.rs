@@ -0,0 +1,58 @@
+//! Finds assets on disk without a masterbundle, and merges in the folders one lists.
+
+mod common;
+
+use torture_parser::discovery;
+use torture_parser::source::FileSystem;
+
+use common::fixture_dir;
+
+#[test]
+fn finds_both_data_file_names() {
+    let root = fixture_dir("Discovery");
+    let found = discovery::find_assets(&FileSystem, &root).unwrap();
+
+    // Assets can be nested inside others, and folders without a data file are not assets.
+    assert_eq!(
+        found,
+        [
+            root.join("Items/Canteen/Asset.dat"),
+            root.join("Items/Flare/Asset.dat"),
+            root.join("Items/Ration/Effects/Crumbs/Crumbs.dat"),
+            root.join("Items/Ration/Ration.dat"),
+            root.join("Spawns/Tables.asset"),
+        ]
+    );
+}
+
+#[test]
+fn merges_listed_folders() {
+    let root = fixture_dir("Discovery");
+    let mut found = discovery::find_assets(&FileSystem, &root).unwrap();
+    found.retain(|data_file| !data_file.ends_with("Ration/Ration.dat"));
+
+    // Folders found on disk are not added twice, and listed folders without a data file are
+    // skipped.
+    discovery::merge_listed(
+        &FileSystem,
+        &mut found,
+        &root,
+        [
+            "Items/Ration",
+            "Items/Ration/Effects/Crumbs",
+            "Items/Missing",
+            "",
+        ],
+    );
+
+    assert_eq!(
+        found,
+        [
+            root.join("Items/Canteen/Asset.dat"),
+            root.join("Items/Flare/Asset.dat"),
+            root.join("Items/Ration/Effects/Crumbs/Crumbs.dat"),
+            root.join("Items/Ration/Ration.dat"),
+            root.join("Spawns/Tables.asset"),
+        ]
+    );
+}
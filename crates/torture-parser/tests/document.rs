@@ -0,0 +1,75 @@
+//! Reads the v2 `Metadata`/`Asset` format from `tests/fixtures/Discovery`.
+
+mod common;
+
+use torture_parser::parser::assets::throwable::ItemThrowableAsset;
+use torture_parser::parser::assets::{AsBaseAsset, BaseAsset, Rarity, Type};
+use torture_parser::parser::document::{self, Node};
+use torture_parser::parser::guid::Guid;
+use torture_parser::parser::Parser;
+
+use common::{fixture_dir, read_file};
+
+#[test]
+fn wrapped_asset_dat() {
+    let directory = fixture_dir("Discovery").join("Items/Flare");
+    let content = read_file("Discovery", "Items/Flare/Asset.dat");
+    assert!(document::is_wrapped(&content));
+
+    let flare = ItemThrowableAsset::parse(&directory, &content).unwrap();
+    let base = flare.base_asset();
+
+    // GUID and Type come from the Metadata block.
+    assert_eq!(base.guid, Guid(0x55555555555555555555555555555555));
+    assert!(matches!(base.r#type, Type::Throwable));
+    assert!(matches!(base.rarity, Rarity::Uncommon));
+    assert_eq!(base.id, 254);
+    assert_eq!(base.name, "Flare");
+    assert!(flare.is_explosive);
+}
+
+#[test]
+fn asset_file_with_several_assets() {
+    let directory = fixture_dir("Discovery").join("Spawns");
+    let content = read_file("Discovery", "Spawns/Tables.asset");
+
+    let blocks = document::asset_blocks(&content).unwrap();
+    let bases: Vec<_> = blocks
+        .iter()
+        .map(|block| BaseAsset::parse(&directory, block).unwrap())
+        .collect();
+
+    let ids: Vec<_> = bases.iter().map(|base| (base.id, base.guid)).collect();
+    assert_eq!(
+        ids,
+        [
+            (228, Guid(0x66666666666666666666666666666666)),
+            (229, Guid(0x77777777777777777777777777777777)),
+        ]
+    );
+    assert!(bases.iter().all(|base| matches!(base.r#type, Type::Spawn)));
+
+    // Nested values have no legacy form, but are kept by the document itself.
+    assert!(!blocks[0].contains("Tables"));
+    let document = document::parse(&content).unwrap();
+    let table = &document.get("Asset").unwrap().get("Tables").unwrap();
+    let Node::List(tables) = table else {
+        panic!("expected a list, got {:?}", table);
+    };
+    assert_eq!(tables[0].get("Weight").and_then(Node::as_value), Some("10"));
+}
+
+#[test]
+fn legacy_content_is_unchanged() {
+    let content = "GUID 11111111111111111111111111111111\nType Hat\nDescription A {curly} one\n";
+
+    assert!(!document::is_wrapped(content));
+    assert_eq!(document::asset_blocks(content).unwrap(), [content]);
+}
+
+#[test]
+fn malformed_documents() {
+    assert!(document::parse("Asset\n{\n\tID 5\n").is_err());
+    assert!(document::parse("Asset\n{\n\tName \"open\n}\n").is_err());
+    assert!(document::parse("ID 5\n}\n").is_err());
+}
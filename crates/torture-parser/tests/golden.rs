@@ -0,0 +1,97 @@
+//! Parses every fixture in `tests/fixtures` and compares the result against the JSON
+//! snapshot of the same name in `tests/golden`.
+//!
+//! Run with `UPDATE_GOLDEN=1` to rewrite the snapshots after an intended change.
+
+mod common;
+
+use torture_parser::parser::assets::bag::ItemBagAsset;
+use torture_parser::parser::assets::barrel::ItemBarrelAsset;
+use torture_parser::parser::assets::barricade::ItemBarricadeAsset;
+use torture_parser::parser::assets::caliber::ItemCaliberAsset;
+use torture_parser::parser::assets::charge::ItemChargeAsset;
+use torture_parser::parser::assets::clothing::ItemClothingAsset;
+use torture_parser::parser::assets::consumable::ItemConsumableAsset;
+use torture_parser::parser::assets::gear::ItemGearAsset;
+use torture_parser::parser::assets::glasses::ItemGlassesAsset;
+use torture_parser::parser::assets::grip::ItemGripAsset;
+use torture_parser::parser::assets::gun::ItemGunAsset;
+use torture_parser::parser::assets::magazine::ItemMagazineAsset;
+use torture_parser::parser::assets::mask::ItemMaskAsset;
+use torture_parser::parser::assets::melee::ItemMeleeAsset;
+use torture_parser::parser::assets::optic::ItemOpticAsset;
+use torture_parser::parser::assets::parachute::ItemParachuteAsset;
+use torture_parser::parser::assets::sentry::ItemSentryAsset;
+use torture_parser::parser::assets::shirt::ItemShirtAsset;
+use torture_parser::parser::assets::sight::ItemSightAsset;
+use torture_parser::parser::assets::storage::ItemStorageAsset;
+use torture_parser::parser::assets::structure::ItemStructureAsset;
+use torture_parser::parser::assets::tactical::ItemTacticalAsset;
+use torture_parser::parser::assets::throwable::ItemThrowableAsset;
+use torture_parser::parser::assets::weapon::ItemWeaponAsset;
+use torture_parser::parser::assets::BaseAsset;
+use torture_parser::parser::level::Level;
+
+use common::{fixture_dir, parse, tests_dir};
+
+fn assert_golden<T: serde::Serialize>(name: &str, value: &T) {
+    let golden = tests_dir().join("golden").join(format!("{}.json", name));
+    let actual = serde_json::to_string_pretty(value).unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(golden.parent().unwrap()).unwrap();
+        std::fs::write(&golden, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&golden)
+        .unwrap_or_else(|_| panic!("Missing snapshot {}", golden.display()));
+    assert_eq!(expected, actual, "{} does not match its snapshot", name);
+}
+
+macro_rules! golden {
+    ($name:ident, $type:ty, $fixture:literal) => {
+        #[test]
+        fn $name() {
+            let asset: $type = parse($fixture);
+            assert_golden(stringify!($name), &asset);
+        }
+    };
+}
+
+golden!(base_asset, BaseAsset, "Maplestrike");
+golden!(weapon, ItemWeaponAsset, "Maplestrike");
+golden!(gun, ItemGunAsset, "Maplestrike");
+golden!(caliber, ItemCaliberAsset, "Military_Magazine");
+golden!(magazine, ItemMagazineAsset, "Military_Magazine");
+golden!(explosive_magazine, ItemMagazineAsset, "Explosive_Magazine");
+golden!(sight, ItemSightAsset, "Eight_Power_Scope");
+golden!(sight_legacy, ItemSightAsset, "Holographic_Sight");
+golden!(tactical, ItemTacticalAsset, "Tactical_Light");
+golden!(grip, ItemGripAsset, "Vertical_Grip");
+golden!(bipod, ItemGripAsset, "Bipod");
+golden!(barrel, ItemBarrelAsset, "Suppressor");
+golden!(optic, ItemOpticAsset, "Binoculars");
+golden!(melee, ItemMeleeAsset, "Machete");
+golden!(melee_light, ItemMeleeAsset, "Flashlight_Baton");
+golden!(throwable, ItemThrowableAsset, "Frag_Grenade");
+golden!(charge, ItemChargeAsset, "Charge");
+golden!(barricade, ItemBarricadeAsset, "Wooden_Plate");
+golden!(storage, ItemStorageAsset, "Metal_Locker");
+golden!(sentry, ItemSentryAsset, "Sentry");
+golden!(structure, ItemStructureAsset, "Metal_Wall");
+golden!(consumable, ItemConsumableAsset, "Bandage");
+golden!(clothing, ItemClothingAsset, "Military_Helmet");
+golden!(shirt, ItemShirtAsset, "Police_Top");
+golden!(bag, ItemBagAsset, "Alicepack");
+golden!(gear, ItemGearAsset, "Military_Helmet");
+golden!(mask, ItemMaskAsset, "Gas_Mask");
+golden!(glasses, ItemGlassesAsset, "Military_Nightvision");
+golden!(glasses_headlamp, ItemGlassesAsset, "Headlamp");
+golden!(parachute, ItemParachuteAsset, "Parachute");
+
+#[test]
+fn level() {
+    let level = Level::parse_level(&fixture_dir("Russia")).unwrap();
+    assert_golden("level", &level);
+}
@@ -0,0 +1,56 @@
+//! Parsing the map folder in `tests/fixtures/Russia`.
+
+mod common;
+
+use torture_parser::parser::guid::Guid;
+use torture_parser::parser::level::info::{LevelSize, LevelType};
+use torture_parser::parser::level::Level;
+use torture_parser::parser::reference::AssetReference;
+
+fn russia() -> Level {
+    Level::parse_level(&common::fixture_dir("Russia")).unwrap()
+}
+
+#[test]
+fn reads_config() {
+    let config = russia().config;
+
+    assert_eq!(config.version, "3.24.0.0");
+    assert_eq!(config.item, 1100);
+    assert_eq!(config.category, "Official");
+    assert_eq!(
+        config.asset.unwrap().guid,
+        "0f1e2d3c4b5a69788796a5b4c3d2e1f0".parse::<Guid>().unwrap()
+    );
+
+    let overrides = &config.asset_overrides;
+    assert_eq!(overrides.len(), 1);
+    assert_eq!(
+        overrides[0].original,
+        "7691f71f76e926e5d981d7e76ed779fc".parse::<Guid>().unwrap()
+    );
+    assert_eq!(config.item_id_mappings.get(&363), Some(&1362));
+    assert_eq!(
+        config.magazine_replacements[0].replacement,
+        AssetReference::from_id(1165)
+    );
+
+    // Keys without a field of their own are kept as they are.
+    assert!(config.other.contains_key("Gravity"));
+    assert!(!config.other.contains_key("Item_ID_Mappings"));
+}
+
+#[test]
+fn reads_info_and_spawn_tables() {
+    let level = russia();
+
+    assert_eq!(level.name, "Russia");
+    assert_eq!(level.info.version, 2);
+    assert!(matches!(level.info.size, LevelSize::Large));
+    assert!(matches!(level.info.r#type, LevelType::Survival));
+
+    let military = &level.item_tables[0];
+    assert_eq!(military.name, "Military");
+    assert_eq!(military.table_id, 228);
+    assert_eq!(military.tiers[0].ids, [363, 364]);
+}
@@ -0,0 +1,75 @@
+//! Linting the mod in `tests/fixtures/Lint` against a few vanilla assets.
+
+mod common;
+
+use torture_parser::lint::{self, Lint, Rule, Severity};
+use torture_parser::parser::assets::Asset;
+use torture_parser::source::FileSystem;
+
+use common::{fixture_dir, parse};
+
+fn vanilla() -> Vec<Asset> {
+    vec![parse("Maplestrike"), parse("Military_Magazine")]
+}
+
+/// Each lint as `(rule, file name, line)`.
+fn summary(lints: &[Lint]) -> Vec<(Rule, String, Option<usize>)> {
+    lints
+        .iter()
+        .map(|lint| {
+            let file = lint.file.file_name().unwrap().to_string_lossy().to_string();
+            (lint.rule, file, lint.line)
+        })
+        .collect()
+}
+
+#[test]
+fn finds_mistakes() {
+    let lints = lint::lint(&FileSystem, &fixture_dir("Lint"), &vanilla()).unwrap();
+
+    let drum = "Drum.dat".to_string();
+    let rifle = "Rifle.dat".to_string();
+    let worn = "Worn.dat".to_string();
+    assert_eq!(
+        summary(&lints),
+        [
+            (Rule::MissingGuid, drum.clone(), Some(4)),
+            (Rule::IdCollision, drum.clone(), Some(4)),
+            (Rule::UnusedCaliber, drum, Some(7)),
+            (Rule::MissingLocalization, rifle.clone(), None),
+            (Rule::GuidCollision, rifle.clone(), Some(1)),
+            (Rule::MissingReference, rifle.clone(), Some(13)),
+            (Rule::DuplicateKey, rifle.clone(), Some(22)),
+            (Rule::UnknownKey, rifle.clone(), Some(23)),
+            (Rule::UnknownKey, rifle, Some(24)),
+            (Rule::OutOfRange, worn, Some(7)),
+        ]
+    );
+
+    // Keys close to a known key are probably typos, others are only reported.
+    let unknown: Vec<_> = lints
+        .iter()
+        .filter(|lint| lint.rule == Rule::UnknownKey)
+        .collect();
+    assert_eq!(
+        unknown[0].help.as_deref(),
+        Some("did you mean `Player_Damage`?")
+    );
+    assert_eq!(unknown[0].severity(), Severity::Warning);
+    assert_eq!(unknown[1].message, "`Bayonet_Length` is not read");
+    assert_eq!(unknown[1].help, None);
+
+    let wear = lints.last().unwrap().to_string();
+    assert!(wear.starts_with("error[out-of-range]: `Wear` is 300, which does not fit in u8\n"));
+    assert!(wear.contains("Worn.dat:7\n   = help: use a value from 0 to 255"));
+}
+
+#[test]
+fn vanilla_rules_need_vanilla() {
+    let lints = lint::lint(&FileSystem, &fixture_dir("Lint"), &[]).unwrap();
+
+    assert!(!lints.iter().any(|lint| matches!(
+        lint.rule,
+        Rule::MissingReference | Rule::UnusedCaliber | Rule::GuidCollision
+    )));
+}
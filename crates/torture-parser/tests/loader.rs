@@ -0,0 +1,69 @@
+//! Loading vanilla, workshop and server roots in order, from `tests/fixtures/Load_Order`.
+
+mod common;
+
+use torture_parser::loader::{AssetCategory, Loader, Origin};
+
+use common::fixture_dir;
+
+#[test]
+fn overrides_follow_load_order() {
+    let root = fixture_dir("Load_Order");
+    let catalog = Loader::new()
+        .vanilla(root.join("vanilla"))
+        .workshop(root.join("workshop"))
+        .unwrap()
+        .server(root.join("server"))
+        .load()
+        .unwrap();
+
+    let names: Vec<_> = catalog
+        .assets
+        .iter()
+        .map(|asset| (asset.origin.clone(), asset.base.name.as_str()))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            (Origin::Vanilla, "Muzzle"),
+            (Origin::Workshop(2000), "Maplestrike+"),
+            (Origin::Server, "Server Bandana"),
+        ]
+    );
+
+    let conflicts: Vec<_> = catalog.conflicts.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        conflicts,
+        [
+            "mod 1000 item 11111111111111111111111111111111 (Red Bandana) shadows vanilla item 11111111111111111111111111111111 (Bandana)",
+            "mod 2000 item 363 (Maplestrike+) shadows vanilla item 363 (Maplestrike)",
+            "server item 228 (Server Bandana) shadows mod 1000 item 228 (Red Bandana)",
+        ]
+    );
+
+    // The effect shares its ID with the gun, but IDs are per category.
+    assert_eq!(
+        catalog.by_id(AssetCategory::Effect, 363).unwrap().base.name,
+        "Muzzle"
+    );
+    assert_eq!(
+        catalog.by_id(AssetCategory::Item, 363).unwrap().base.name,
+        "Maplestrike+"
+    );
+
+    assert_eq!(catalog.failures.len(), 1);
+    assert!(catalog.failures[0].data_file.ends_with("Broken.dat"));
+}
+
+#[test]
+fn workshop_order_is_configurable() {
+    let root = fixture_dir("Load_Order");
+    let loader = Loader::new()
+        .workshop_ordered(root.join("workshop/content/304930"), &[2000, 1000, 3000])
+        .unwrap();
+
+    let origins: Vec<_> = loader.roots().iter().map(|root| &root.origin).collect();
+    assert_eq!(origins, [&Origin::Workshop(2000), &Origin::Workshop(1000)]);
+    assert!(loader.roots()[0].path.ends_with("2000/Bundles"));
+    assert!(loader.roots()[1].path.ends_with("1000"));
+}
@@ -0,0 +1,141 @@
+//! Fitting attachments to the Maplestrike fixture, which has every hook, attachment caliber 1,
+//! and magazine calibers 1 and 4.
+
+mod common;
+
+use torture_parser::loadout::Loadout;
+use torture_parser::parser::assets::barrel::ItemBarrelAsset;
+use torture_parser::parser::assets::grip::ItemGripAsset;
+use torture_parser::parser::assets::gun::{Hook, ItemGunAsset};
+use torture_parser::parser::assets::magazine::ItemMagazineAsset;
+use torture_parser::parser::assets::sight::ItemSightAsset;
+
+use common::{assert_close, parse};
+
+/// Stats are checked to four decimal places.
+const TOLERANCE: f32 = 1e-4;
+
+#[test]
+fn check() {
+    let mut gun: ItemGunAsset = parse("Maplestrike");
+    let mut scope: ItemSightAsset = parse("Eight_Power_Scope");
+    let grip: ItemGripAsset = parse("Vertical_Grip");
+    let mut magazine: ItemMagazineAsset = parse("Military_Magazine");
+
+    let loadout = Loadout {
+        sight: Some(&scope),
+        grip: Some(&grip),
+        magazine: Some(&magazine),
+        ..Loadout::new(&gun)
+    };
+    loadout.check().unwrap();
+
+    // Attachments are checked against the gun's attachment calibers, and magazines against its
+    // magazine calibers.
+    scope.item_caliber_asset.calibers = vec![4];
+    let loadout = Loadout {
+        sight: Some(&scope),
+        ..Loadout::new(&gun)
+    };
+    let error = loadout.check().unwrap_err().to_string();
+    assert!(error.contains("shares no caliber"), "{}", error);
+
+    magazine.item_caliber_asset.calibers = vec![4];
+    let loadout = Loadout {
+        magazine: Some(&magazine),
+        ..Loadout::new(&gun)
+    };
+    loadout.check().unwrap();
+
+    magazine.item_caliber_asset.calibers = vec![2];
+    let loadout = Loadout {
+        magazine: Some(&magazine),
+        ..Loadout::new(&gun)
+    };
+    let error = loadout.check().unwrap_err().to_string();
+    assert!(error.contains("shares no caliber"), "{}", error);
+
+    // Attachments without calibers fit any gun, as long as it has the hook.
+    scope.item_caliber_asset.calibers = Vec::new();
+    gun.hooks.retain(|hook| *hook != Hook::Grip);
+    let loadout = Loadout {
+        sight: Some(&scope),
+        ..Loadout::new(&gun)
+    };
+    loadout.check().unwrap();
+
+    let loadout = Loadout {
+        grip: Some(&grip),
+        ..Loadout::new(&gun)
+    };
+    let error = loadout.check().unwrap_err().to_string();
+    assert_eq!(error, "Maplestrike has no Grip hook for Vertical Grip");
+}
+
+#[test]
+fn stats() {
+    let gun: ItemGunAsset = parse("Maplestrike");
+    let scope: ItemSightAsset = parse("Eight_Power_Scope");
+    let grip: ItemGripAsset = parse("Vertical_Grip");
+    let suppressor: ItemBarrelAsset = parse("Suppressor");
+    let magazine: ItemMagazineAsset = parse("Military_Magazine");
+
+    let stats = Loadout::new(&gun).stats();
+    assert_eq!(stats.firerate, 5);
+    assert_eq!(stats.zoom, 1.0);
+    assert!(stats.changes.is_empty());
+
+    let loadout = Loadout {
+        sight: Some(&scope),
+        grip: Some(&grip),
+        barrel: Some(&suppressor),
+        magazine: Some(&magazine),
+        ..Loadout::new(&gun)
+    };
+    let stats = loadout.stats();
+
+    assert_eq!(stats.firerate, 4);
+    assert_eq!(stats.zoom, 8.0);
+    assert_eq!(stats.pellets, 1);
+    assert_close(stats.damage_multiplier, 0.95 * 1.05, TOLERANCE);
+    assert_close(stats.spread.aim, 0.016, TOLERANCE);
+    assert_close(stats.recoil.max_y, 1.2, TOLERANCE);
+    assert_close(stats.sway_multiplier, 0.8, TOLERANCE);
+    assert_close(stats.aim_in_duration, 0.225, TOLERANCE);
+    assert_close(stats.ballistic_drop, 0.0036, TOLERANCE);
+    assert_close(stats.reload_time, 2.5 / 1.1, TOLERANCE);
+
+    // Changes are recorded by sight, grip, barrel and magazine, and the barrel's drop and the
+    // magazine's reload speed last.
+    let changes: Vec<(&str, &str)> = stats
+        .changes
+        .iter()
+        .map(|change| (change.attachment.as_str(), change.stat.as_str()))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            ("8x Scope", "sway_multiplier"),
+            ("8x Scope", "aiming_movement_speed_multiplier"),
+            ("Vertical Grip", "spread.aim"),
+            ("Vertical Grip", "spread.hip"),
+            ("Vertical Grip", "recoil.min_x"),
+            ("Vertical Grip", "recoil.max_x"),
+            ("Vertical Grip", "recoil.min_y"),
+            ("Vertical Grip", "recoil.max_y"),
+            ("Vertical Grip", "recoil.aim"),
+            ("Vertical Grip", "firerate"),
+            ("Suppressor", "damage_multiplier"),
+            ("Military Magazine", "damage_multiplier"),
+            ("Military Magazine", "aim_in_duration"),
+            ("Suppressor", "ballistic_drop"),
+            ("Military Magazine", "reload_time"),
+        ]
+    );
+
+    let firerate = &stats.changes[9];
+    assert_eq!((firerate.before, firerate.after), (5.0, 4.0));
+    let recoil = &stats.changes[6];
+    assert_close(recoil.before, 1.0, TOLERANCE);
+    assert_close(recoil.after, 0.6, TOLERANCE);
+}
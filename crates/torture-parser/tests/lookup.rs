@@ -0,0 +1,149 @@
+//! Looking up fixtures with [`Lookup`], along with the blueprints and spawn assets it reads.
+
+mod common;
+
+use torture_parser::loader::AssetCategory;
+use torture_parser::lookup::Lookup;
+use torture_parser::parser::assets::{AsBaseAsset, Asset};
+use torture_parser::parser::reference::AssetReference;
+use torture_parser::parser::spawn::{SpawnAsset, SpawnEntry, SpawnTarget};
+use torture_parser::source::FileSystem;
+
+use common::{fixture_dir, parse, read};
+
+/// The spawn assets of `Discovery/Spawns/Tables.asset` and `Military_Guns`.
+fn spawns() -> Vec<Asset> {
+    let tables = fixture_dir("Discovery").join("Spawns");
+    let content = std::fs::read_to_string(tables.join("Tables.asset")).unwrap();
+
+    let mut parsed = Asset::parse_all(&FileSystem, &tables, &content).unwrap();
+    parsed.extend(
+        Asset::parse_all(
+            &FileSystem,
+            &fixture_dir("Military_Guns"),
+            &read("Military_Guns"),
+        )
+        .unwrap(),
+    );
+
+    parsed.into_iter().map(|parsed| parsed.unwrap().0).collect()
+}
+
+fn names<'a, T: AsBaseAsset + 'a>(assets: impl IntoIterator<Item = &'a T>) -> Vec<String> {
+    assets
+        .into_iter()
+        .map(|asset| asset.name().to_string())
+        .collect()
+}
+
+#[test]
+fn reads_blueprints() {
+    let bandage: Asset = parse("Bandage");
+    let blueprints = &bandage.base_asset().blueprints;
+    assert_eq!(blueprints.len(), 2);
+
+    let craft = &blueprints[0];
+    assert_eq!(craft.r#type, "Supply");
+    assert_eq!(craft.supplies[0].item, AssetReference::from_id(66));
+    assert_eq!(craft.supplies[0].amount, 2);
+    // Without a `Product` or `Outputs`, the blueprint makes the item itself.
+    assert_eq!(craft.outputs[0].item, AssetReference::from_id(95));
+    assert_eq!(craft.outputs[0].amount, 2);
+    assert!(craft.tool.is_none());
+
+    let salvage = &blueprints[1];
+    assert_eq!(salvage.supplies[0].amount, 1);
+    assert_eq!(salvage.outputs[0].item, AssetReference::from_id(66));
+    assert_eq!(salvage.outputs[0].amount, 3);
+    assert_eq!(salvage.tool, AssetReference::from_id(76));
+    assert_eq!((salvage.skill.as_str(), salvage.level), ("Craft", 1));
+}
+
+#[test]
+fn reads_spawn_tables() {
+    let spawns: Vec<SpawnAsset> = spawns()
+        .into_iter()
+        .map(|asset| match asset {
+            Asset::Spawn(spawn) => spawn,
+            asset => panic!("{} is not a spawn asset", asset.name()),
+        })
+        .collect();
+
+    // `Tables.asset` holds two spawn assets in the v2 format, named after their folder.
+    assert_eq!(names(&spawns), ["Spawns", "Spawns", "Military_Guns"]);
+    assert_eq!(
+        spawns[0].tables,
+        [SpawnEntry {
+            target: SpawnTarget::Asset(363),
+            weight: 10,
+        }]
+    );
+    assert!(spawns[1].tables.is_empty());
+    assert_eq!(
+        spawns[2].tables,
+        [
+            SpawnEntry {
+                target: SpawnTarget::Spawn(228),
+                weight: 10,
+            },
+            SpawnEntry {
+                target: SpawnTarget::Asset(121),
+                weight: 5,
+            },
+        ]
+    );
+}
+
+#[test]
+fn looks_up_assets() {
+    let parsed: Vec<Asset> = [
+        "Maplestrike",
+        "Military_Magazine",
+        "Holographic_Sight",
+        "Vertical_Grip",
+        "Machete",
+        "Bandage",
+    ]
+    .iter()
+    .map(|fixture| parse(fixture))
+    .chain(spawns())
+    .collect();
+    let assets: Vec<&Asset> = parsed.iter().collect();
+    let lookup = Lookup::new(&assets);
+
+    assert_eq!(lookup.find("363"), [0]);
+    assert_eq!(lookup.find("7d7d76683dde27f86d063a199550c75d"), [5]);
+    assert_eq!(lookup.find("machete"), [4]);
+    assert_eq!(lookup.find("ma"), [0, 1, 4]);
+    assert!(lookup.find("Cloth").is_empty());
+
+    let Asset::Gun(gun) = &parsed[0] else {
+        panic!("Maplestrike is not a gun");
+    };
+    assert!(lookup
+        .resolve(gun.magazine_id, AssetCategory::Effect)
+        .is_none());
+    let magazine = lookup.resolve(gun.magazine_id, AssetCategory::Item);
+    assert_eq!(
+        magazine.map(|asset| asset.name()),
+        Some("Military Magazine")
+    );
+
+    assert_eq!(
+        names(lookup.attachments(gun)),
+        ["Military Magazine", "Holographic Sight", "Vertical Grip"]
+    );
+
+    // Military_Guns rolls the Maplestrike through spawn 228, and the Machete directly.
+    assert_eq!(
+        names(lookup.spawns_of(&parsed[0])),
+        ["Spawns", "Military_Guns"]
+    );
+    assert_eq!(names(lookup.spawns_of(&parsed[4])), ["Military_Guns"]);
+    assert!(lookup.spawns_of(&parsed[5]).is_empty());
+
+    // Legacy IDs in tables are items, so a spawn asset with the Maplestrike's ID is not rolled.
+    let mut spawn = spawns().remove(1);
+    spawn.base_asset_mut().id = 363;
+    assert!(lookup.spawns_of(&spawn).is_empty());
+}
@@ -0,0 +1,73 @@
+//! Filtering the fixtures with [`Filter`] expressions.
+
+mod common;
+
+use torture_parser::parser::assets::{AsBaseAsset, Asset};
+use torture_parser::query::{self, Filter};
+
+use common::parse;
+
+fn assets() -> Vec<Asset> {
+    vec![parse("Maplestrike"), parse("Machete"), parse("Bandage")]
+}
+
+/// Names of the fixtures matching `filter`.
+fn names(filter: &str) -> Vec<String> {
+    let filter: Filter = filter.parse().unwrap();
+
+    assets()
+        .iter()
+        .filter(|asset| filter.matches_asset(asset))
+        .map(|asset| asset.base_asset().name.clone())
+        .collect()
+}
+
+#[test]
+fn filters_assets() {
+    assert_eq!(
+        names("type = Gun and player_damage.amount > 39 and firemodes contains Auto"),
+        ["Maplestrike"]
+    );
+    assert_eq!(
+        names("player_damage.amount >= 40"),
+        ["Maplestrike", "Machete"]
+    );
+    assert_eq!(
+        names("name matches '^ma' or kind == consumable"),
+        ["Bandage"]
+    );
+    assert_eq!(names("name ~ '^Ma' and not invulnerable"), ["Maplestrike"]);
+    assert_eq!(names("not (type = gun or type = melee)"), ["Bandage"]);
+    assert_eq!(names("range = 2.25 and rarity != Rare"), ["Machete"]);
+    assert_eq!(names("name contains \"ACHE\""), ["Machete"]);
+    assert_eq!(names("health"), ["Bandage"]);
+
+    // A missing field never matches, even when negated in the comparison.
+    assert!(names("no_such_field != 1").is_empty());
+}
+
+#[test]
+fn sorts_fields() {
+    let mut fields: Vec<_> = assets().iter().map(query::fields).collect();
+    fields.sort_by(|a, b| query::compare_values(&a["range"], &b["range"]));
+
+    let names: Vec<_> = fields.iter().map(|fields| fields["name"].clone()).collect();
+    assert_eq!(names, ["Bandage", "Machete", "Maplestrike"]);
+    assert_eq!(query::value_text(&fields[1]["range"]), "2.25");
+    assert!(!fields[0].contains_key("base"));
+}
+
+#[test]
+fn rejects_invalid_filters() {
+    let error = |filter: &str| filter.parse::<Filter>().unwrap_err().to_string();
+
+    assert_eq!(error("type ="), "Expected a value after `type`");
+    assert_eq!(error("(type = Gun"), "Missing `)` in the filter");
+    assert_eq!(error("= Gun"), "Expected a field, found `=`");
+    assert_eq!(
+        error("type = Gun Melee"),
+        "Unexpected `Melee` after the end of the filter"
+    );
+    assert_eq!(error("name = 'Maple"), "Missing closing ' in the filter");
+    assert_eq!(error("name ~ '['"), "Failed to parse regex for `name`");
+}
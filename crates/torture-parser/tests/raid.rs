@@ -0,0 +1,116 @@
+//! Raiding the Wooden_Plate, Metal_Locker and Metal_Wall fixtures.
+
+mod common;
+
+use torture_parser::damage::ArmorTierMultipliers;
+use torture_parser::parser::assets::barricade::ItemBarricadeAsset;
+use torture_parser::parser::assets::charge::ItemChargeAsset;
+use torture_parser::parser::assets::magazine::ItemMagazineAsset;
+use torture_parser::parser::assets::melee::ItemMeleeAsset;
+use torture_parser::parser::assets::storage::ItemStorageAsset;
+use torture_parser::parser::assets::structure::ItemStructureAsset;
+use torture_parser::parser::assets::throwable::ItemThrowableAsset;
+use torture_parser::raid::{Buildable, RaidTool};
+
+use common::parse;
+
+struct Tools {
+    explosive_magazine: ItemMagazineAsset,
+    military_magazine: ItemMagazineAsset,
+    machete: ItemMeleeAsset,
+    grenade: ItemThrowableAsset,
+    charge: ItemChargeAsset,
+}
+
+impl Tools {
+    fn parse() -> Tools {
+        Tools {
+            explosive_magazine: parse("Explosive_Magazine"),
+            military_magazine: parse("Military_Magazine"),
+            machete: parse("Machete"),
+            grenade: parse("Frag_Grenade"),
+            charge: parse("Charge"),
+        }
+    }
+
+    fn all(&self) -> Vec<RaidTool<'_>> {
+        vec![
+            RaidTool::Magazine(&self.explosive_magazine),
+            RaidTool::Magazine(&self.military_magazine),
+            RaidTool::Melee(&self.machete),
+            RaidTool::Throwable(&self.grenade),
+            RaidTool::Charge(&self.charge),
+        ]
+    }
+}
+
+/// Uses of each tool, in the order of [`Tools::all`].
+fn counts(buildable: Buildable, tools: &Tools, tiers: ArmorTierMultipliers) -> Vec<Option<u32>> {
+    buildable
+        .raid_costs(&tools.all(), tiers)
+        .into_iter()
+        .map(|cost| cost.count)
+        .collect()
+}
+
+#[test]
+fn vulnerable_barricade() {
+    let tools = Tools::parse();
+    let plate: ItemBarricadeAsset = parse("Wooden_Plate");
+    let plate = Buildable::Barricade(&plate);
+    assert_eq!(plate.health(), 400);
+
+    // Every tool damages it, except magazines that are not `Explosive`.
+    let costs = plate.raid_costs(&tools.all(), ArmorTierMultipliers::default());
+    let damage: Vec<f32> = costs.iter().map(|cost| cost.damage).collect();
+    assert_eq!(damage, [100.0, 0.0, 15.0, 300.0, 1000.0]);
+    assert_eq!(
+        counts(plate, &tools, ArmorTierMultipliers::default()),
+        [Some(4), None, Some(27), Some(2), Some(1)]
+    );
+}
+
+#[test]
+fn armor_tiers() {
+    let tools = Tools::parse();
+    let locker: ItemStorageAsset = parse("Metal_Locker");
+    let locker = Buildable::Barricade(&locker.item_barricade_asset);
+    assert_eq!(locker.health(), 800);
+
+    // The locker is high tier, so only takes half of the damage of explosions by default, and
+    // none from anything else as it is not `Vulnerable`.
+    assert_eq!(
+        counts(locker, &tools, ArmorTierMultipliers::default()),
+        [Some(16), None, None, Some(6), Some(2)]
+    );
+
+    let tiers = ArmorTierMultipliers {
+        low_tier: 2.0,
+        high_tier: 1.0,
+    };
+    assert_eq!(
+        counts(locker, &tools, tiers),
+        [Some(8), None, None, Some(3), Some(1)]
+    );
+}
+
+#[test]
+fn explosion_proof_structure() {
+    let tools = Tools::parse();
+    let wall: ItemStructureAsset = parse("Metal_Wall");
+    let wall = Buildable::Structure(&wall);
+    assert_eq!(wall.health(), 1000);
+
+    // Neither `Vulnerable` nor damaged by explosions, so nothing can destroy it.
+    assert_eq!(
+        counts(wall, &tools, ArmorTierMultipliers::default()),
+        [None; 5]
+    );
+
+    let charge = RaidTool::Charge(&tools.charge);
+    assert_eq!(
+        charge.damage_against(&wall, ArmorTierMultipliers::default()),
+        0.0
+    );
+    assert_eq!(wall.hits_to_destroy(1500.0), Some(1));
+}
@@ -0,0 +1,147 @@
+//! Parses assets from memory and archives instead of the filesystem.
+
+mod common;
+
+use std::path::Path;
+
+use torture_parser::discovery;
+use torture_parser::loader::Loader;
+use torture_parser::parser::assets::gun::ItemGunAsset;
+use torture_parser::parser::assets::AsBaseAsset;
+use torture_parser::parser::dat::DatAsset;
+use torture_parser::parser::level::Level;
+use torture_parser::source::{AssetSource, FileSystem, MemorySource};
+
+use common::{fixture_dir, read};
+
+const BANDANA: &str = "GUID 11111111111111111111111111111111\nType Hat\nID 228\n";
+
+fn mod_upload() -> MemorySource {
+    MemorySource::new()
+        .with("Bundles/Items/Bandana/Bandana.dat", BANDANA)
+        .with("Bundles/Items/Bandana/English.dat", "Name Bandana\n")
+        .with("./Bundles/Items/Readme.txt", "Not an asset")
+}
+
+#[test]
+fn memory_source_layout() {
+    let source = mod_upload();
+
+    assert!(source.is_file(Path::new("Bundles/Items/Bandana/English.dat")));
+    assert!(source.is_dir(Path::new("Bundles/Items")));
+    assert!(!source.is_dir(Path::new("Bundles/Items/Readme.txt")));
+    assert_eq!(
+        source.read_dir(Path::new("Bundles/Items")).unwrap(),
+        [
+            Path::new("Bundles/Items/Bandana"),
+            Path::new("Bundles/Items/Readme.txt"),
+        ]
+    );
+    assert!(source.read(Path::new("Bundles/Missing.dat")).is_err());
+}
+
+#[test]
+fn parses_from_memory() {
+    let source = mod_upload();
+    let found = discovery::find_assets(&source, Path::new("Bundles")).unwrap();
+    assert_eq!(found, [Path::new("Bundles/Items/Bandana/Bandana.dat")]);
+
+    let catalog = Loader::with_source(source)
+        .vanilla("Bundles")
+        .load()
+        .unwrap();
+    assert_eq!(catalog.assets.len(), 1);
+    assert_eq!(catalog.assets[0].base.name, "Bandana");
+    assert_eq!(catalog.assets[0].base.id, 228);
+}
+
+#[test]
+fn memory_matches_filesystem() {
+    let directory = fixture_dir("Maplestrike");
+    let content = read("Maplestrike");
+
+    let source = MemorySource::new().with(
+        "Maplestrike/English.dat",
+        FileSystem.read(&directory.join("English.dat")).unwrap(),
+    );
+    let (from_memory, _) =
+        ItemGunAsset::parse_from(&source, Path::new("Maplestrike"), &content).unwrap();
+    let (from_disk, _) = ItemGunAsset::parse_with_diagnostics(&directory, &content).unwrap();
+
+    assert_eq!(from_memory.name(), "Maplestrike");
+    assert_eq!(
+        serde_json::to_value(&from_memory).unwrap(),
+        serde_json::to_value(&from_disk).unwrap()
+    );
+}
+
+#[test]
+fn level_from_memory() {
+    let russia = fixture_dir("Russia");
+    let mut source = MemorySource::new();
+    for file in ["Config.json", "Level.dat", "Spawns/Items.dat"] {
+        source.insert(
+            Path::new("Maps/Russia").join(file),
+            std::fs::read(russia.join(file)).unwrap(),
+        );
+    }
+
+    let level = Level::read_level(&source, Path::new("Maps/Russia")).unwrap();
+    assert_eq!(level.name, "Russia");
+    assert!(!level.item_tables.is_empty());
+    assert!(level.vehicle_tables.is_empty());
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn parses_from_zip() {
+    use std::io::{Cursor, Write};
+
+    let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default();
+    for (path, content) in [
+        ("Bandana/Bandana.dat", BANDANA),
+        ("Bandana/English.dat", "Name Bandana\n"),
+        ("../Escape/Escape.dat", BANDANA),
+    ] {
+        archive.start_file(path, options).unwrap();
+        archive.write_all(content.as_bytes()).unwrap();
+    }
+    let archive = archive.finish().unwrap();
+
+    let source = MemorySource::from_zip(Cursor::new(archive.into_inner())).unwrap();
+    let files: Vec<_> = source.files().collect();
+    assert_eq!(
+        files,
+        [
+            Path::new("Bandana/Bandana.dat"),
+            Path::new("Bandana/English.dat"),
+        ]
+    );
+
+    let catalog = Loader::with_source(source).vanilla("").load().unwrap();
+    assert_eq!(catalog.assets[0].base.name, "Bandana");
+}
+
+#[cfg(feature = "tar")]
+#[test]
+fn parses_from_tar() {
+    let mut archive = tar::Builder::new(Vec::new());
+    for (path, content) in [
+        ("Bandana/Bandana.dat", BANDANA),
+        ("Bandana/English.dat", "Name Bandana\n"),
+    ] {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        archive
+            .append_data(&mut header, path, content.as_bytes())
+            .unwrap();
+    }
+    let archive = archive.into_inner().unwrap();
+
+    let source = MemorySource::from_tar(archive.as_slice()).unwrap();
+    let catalog = Loader::with_source(source).vanilla("").load().unwrap();
+    assert_eq!(catalog.assets[0].base.name, "Bandana");
+}